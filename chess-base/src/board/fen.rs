@@ -0,0 +1,521 @@
+/// FEN module
+/// Contains the parser and the generator for positions written in Forsyth-Edwards Notation
+/// and the error type returned when a FEN string is malformed
+use super::modifiers::Modifiers;
+use super::Board;
+use crate::core::castles::{Castles, CastlingRights};
+use crate::core::color::Color;
+use crate::core::piece::Piece;
+use crate::core::pieces::Pieces;
+use crate::core::position::Position;
+use std::fmt;
+
+/// The six space separated fields of a FEN string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenField {
+    PiecePlacement,
+    SideToMove,
+    Castling,
+    EnPassant,
+    HalfmoveClock,
+    FullmoveNumber,
+}
+
+/// What exactly is wrong with the field named by a FenError
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenErrorKind {
+    /// The field is missing entirely
+    MissingField,
+    /// The field contains a character that is not allowed there
+    UnexpectedCharacter(char),
+    /// A rank describes more than 8 squares
+    RankTooLong,
+    /// A rank describes less than 8 squares
+    RankTooShort,
+    /// The piece placement does not describe exactly 8 ranks
+    WrongRankCount,
+    /// A side does not have exactly one king
+    InvalidKingCount(Color),
+    /// A pawn is placed on the first or the eighth rank
+    PawnOnBackRank,
+    /// The side that is not to move is in check, so its king could be taken
+    OpponentInCheck,
+    /// The same castling right is listed twice
+    DuplicateCastlingRight(char),
+    /// The en passant square is not on the rank behind a pawn that just moved two squares
+    InvalidEnPassantSquare,
+    /// A clock is not a number or does not fit in its range
+    InvalidNumber,
+    /// There is more input after the last field
+    TrailingInput,
+}
+
+/// Error returned by Board::from_fen
+/// Names the field that could not be parsed and the character offset
+/// in the input string where the problem was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FenError {
+    pub field: FenField,
+    pub offset: usize,
+    pub kind: FenErrorKind,
+}
+
+impl FenError {
+    fn new(field: FenField, offset: usize, kind: FenErrorKind) -> FenError {
+        FenError {
+            field,
+            offset,
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for FenField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenField::PiecePlacement => write!(f, "piece placement"),
+            FenField::SideToMove => write!(f, "side to move"),
+            FenField::Castling => write!(f, "castling rights"),
+            FenField::EnPassant => write!(f, "en passant square"),
+            FenField::HalfmoveClock => write!(f, "halfmove clock"),
+            FenField::FullmoveNumber => write!(f, "fullmove number"),
+        }
+    }
+}
+
+impl fmt::Display for FenErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenErrorKind::MissingField => write!(f, "field is missing"),
+            FenErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            FenErrorKind::RankTooLong => write!(f, "rank has more than 8 squares"),
+            FenErrorKind::RankTooShort => write!(f, "rank has less than 8 squares"),
+            FenErrorKind::WrongRankCount => write!(f, "expected 8 ranks"),
+            FenErrorKind::InvalidKingCount(color) => {
+                write!(f, "{:?} must have exactly one king", color)
+            }
+            FenErrorKind::PawnOnBackRank => write!(f, "pawn on the first or eighth rank"),
+            FenErrorKind::OpponentInCheck => write!(f, "the side not to move is in check"),
+            FenErrorKind::DuplicateCastlingRight(c) => {
+                write!(f, "castling right '{}' is listed twice", c)
+            }
+            FenErrorKind::InvalidEnPassantSquare => write!(f, "invalid en passant square"),
+            FenErrorKind::InvalidNumber => write!(f, "invalid number"),
+            FenErrorKind::TrailingInput => write!(f, "unexpected input after the last field"),
+        }
+    }
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid FEN {} at offset {}: {}",
+            self.field, self.offset, self.kind
+        )
+    }
+}
+
+impl std::error::Error for FenError {}
+
+/// Splits the FEN string into its fields
+/// Every field is returned together with the character offset it starts at
+fn split_fields(fen: &str) -> Vec<(usize, String)> {
+    let mut fields: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+
+    for (offset, c) in fen.chars().enumerate() {
+        if c.is_whitespace() {
+            if let Some(field) = current.take() {
+                fields.push(field);
+            }
+        } else {
+            match current.as_mut() {
+                Some((_, field)) => field.push(c),
+                None => current = Some((offset, c.to_string())),
+            }
+        }
+    }
+
+    if let Some(field) = current {
+        fields.push(field);
+    }
+
+    fields
+}
+
+/// Returns the color and type of the piece a FEN character stands for
+fn piece_from_char(c: char) -> Option<(Color, Pieces)> {
+    let color = if c.is_ascii_uppercase() {
+        Color::White
+    } else {
+        Color::Black
+    };
+
+    let piece_type = match c.to_ascii_lowercase() {
+        'p' => Pieces::Pawn,
+        'n' => Pieces::Knight,
+        'b' => Pieces::Bishop,
+        'r' => Pieces::Rook,
+        'q' => Pieces::Queen,
+        'k' => Pieces::King,
+        _ => return None,
+    };
+
+    Some((color, piece_type))
+}
+
+/// Returns the FEN character for a piece, uppercase for white and lowercase for black
+fn piece_to_char(piece: &Piece) -> char {
+    let c = match piece.get_piece_type() {
+        Pieces::Pawn => 'p',
+        Pieces::Knight => 'n',
+        Pieces::Bishop => 'b',
+        Pieces::Rook => 'r',
+        Pieces::Queen => 'q',
+        Pieces::King => 'k',
+    };
+
+    match piece.get_color() {
+        Color::White => c.to_ascii_uppercase(),
+        Color::Black => c,
+    }
+}
+
+fn parse_placement(offset: usize, field: &str) -> Result<Vec<Piece>, FenError> {
+    let error = |at: usize, kind| FenError::new(FenField::PiecePlacement, offset + at, kind);
+
+    let mut pieces = Vec::new();
+    let mut kings = [0, 0];
+    let mut rank: u8 = 7;
+    let mut file: u8 = 0;
+
+    for (i, c) in field.chars().enumerate() {
+        match c {
+            '/' => {
+                if file < 8 {
+                    return Err(error(i, FenErrorKind::RankTooShort));
+                }
+                if rank == 0 {
+                    return Err(error(i, FenErrorKind::WrongRankCount));
+                }
+                rank -= 1;
+                file = 0;
+            }
+            '1'..='8' => {
+                file += c.to_digit(10).unwrap() as u8;
+                if file > 8 {
+                    return Err(error(i, FenErrorKind::RankTooLong));
+                }
+            }
+            _ => {
+                let (color, piece_type) =
+                    piece_from_char(c).ok_or(error(i, FenErrorKind::UnexpectedCharacter(c)))?;
+
+                if file > 7 {
+                    return Err(error(i, FenErrorKind::RankTooLong));
+                }
+                if piece_type == Pieces::Pawn && (rank == 0 || rank == 7) {
+                    return Err(error(i, FenErrorKind::PawnOnBackRank));
+                }
+                if piece_type == Pieces::King {
+                    match color {
+                        Color::White => kings[0] += 1,
+                        Color::Black => kings[1] += 1,
+                    }
+                }
+
+                let position = Position::new(file, rank);
+                pieces.push(Piece::new(position, color, piece_type));
+                file += 1;
+            }
+        }
+    }
+
+    let end = field.chars().count();
+    if rank != 0 {
+        return Err(error(end, FenErrorKind::WrongRankCount));
+    }
+    if file < 8 {
+        return Err(error(end, FenErrorKind::RankTooShort));
+    }
+    if kings[0] != 1 {
+        return Err(error(0, FenErrorKind::InvalidKingCount(Color::White)));
+    }
+    if kings[1] != 1 {
+        return Err(error(0, FenErrorKind::InvalidKingCount(Color::Black)));
+    }
+
+    Ok(pieces)
+}
+
+fn parse_turn(offset: usize, field: &str) -> Result<Color, FenError> {
+    match field {
+        "w" => Ok(Color::White),
+        "b" => Ok(Color::Black),
+        _ => {
+            let mut chars = field.chars();
+            let first = chars.next().unwrap();
+            let (at, c) = match chars.next() {
+                Some(c) if first == 'w' || first == 'b' => (1, c),
+                _ => (0, first),
+            };
+            Err(FenError::new(
+                FenField::SideToMove,
+                offset + at,
+                FenErrorKind::UnexpectedCharacter(c),
+            ))
+        }
+    }
+}
+
+fn parse_castling(offset: usize, field: &str, modifiers: &mut Modifiers) -> Result<(), FenError> {
+    let error = |at: usize, kind| FenError::new(FenField::Castling, offset + at, kind);
+
+    modifiers.castling_rights = CastlingRights::none();
+
+    if field == "-" {
+        return Ok(());
+    }
+
+    for (i, c) in field.chars().enumerate() {
+        let (color, castle_type) = match c {
+            'K' => (Color::White, Castles::KingSide),
+            'Q' => (Color::White, Castles::QueenSide),
+            'k' => (Color::Black, Castles::KingSide),
+            'q' => (Color::Black, Castles::QueenSide),
+            _ => return Err(error(i, FenErrorKind::UnexpectedCharacter(c))),
+        };
+
+        if modifiers.castling_rights.has(color, castle_type) {
+            return Err(error(i, FenErrorKind::DuplicateCastlingRight(c)));
+        }
+        modifiers.castling_rights.insert(color, castle_type);
+    }
+
+    Ok(())
+}
+
+fn parse_en_passant(
+    offset: usize,
+    field: &str,
+    turn: Color,
+    pieces: &[Piece],
+) -> Result<Option<Position>, FenError> {
+    let error = |at: usize, kind| FenError::new(FenField::EnPassant, offset + at, kind);
+
+    if field == "-" {
+        return Ok(None);
+    }
+
+    let chars: Vec<char> = field.chars().collect();
+
+    let file = chars[0];
+    if !('a'..='h').contains(&file) {
+        return Err(error(0, FenErrorKind::UnexpectedCharacter(file)));
+    }
+
+    let rank = match chars.get(1) {
+        Some(c) if ('1'..='8').contains(c) => c.to_digit(10).unwrap() as u8,
+        Some(c) => return Err(error(1, FenErrorKind::UnexpectedCharacter(*c))),
+        None => return Err(error(1, FenErrorKind::InvalidEnPassantSquare)),
+    };
+
+    if chars.len() > 2 {
+        return Err(error(2, FenErrorKind::UnexpectedCharacter(chars[2])));
+    }
+
+    // the square is behind a pawn of the side that just moved
+    let (expected, pawn_rank, start_rank, opponent) = match turn {
+        Color::White => (6, 5, 7, Color::Black),
+        Color::Black => (3, 4, 2, Color::White),
+    };
+    if rank != expected {
+        return Err(error(1, FenErrorKind::InvalidEnPassantSquare));
+    }
+
+    // that pawn just moved two squares, so it stands in front of the square
+    // and the square it came from is empty, like the square itself
+    let piece_on = |rank: u8| {
+        pieces
+            .iter()
+            .find(|piece| piece.get_position() == Position::from_an(file, rank))
+            .map(|piece| (piece.get_color(), piece.get_piece_type()))
+    };
+    if piece_on(pawn_rank) != Some((opponent, Pieces::Pawn))
+        || piece_on(rank).is_some()
+        || piece_on(start_rank).is_some()
+    {
+        return Err(error(0, FenErrorKind::InvalidEnPassantSquare));
+    }
+
+    Ok(Some(Position::from_an(file, rank)))
+}
+
+fn parse_number<T: std::str::FromStr>(
+    field_type: FenField,
+    offset: usize,
+    field: &str,
+) -> Result<T, FenError> {
+    if let Some((at, c)) = field.chars().enumerate().find(|(_, c)| !c.is_ascii_digit()) {
+        return Err(FenError::new(
+            field_type,
+            offset + at,
+            FenErrorKind::UnexpectedCharacter(c),
+        ));
+    }
+
+    field
+        .parse()
+        .map_err(|_| FenError::new(field_type, offset, FenErrorKind::InvalidNumber))
+}
+
+impl Board {
+    /// Creates a new board from a FEN string
+    /// All six fields are read: piece placement, side to move, castling rights,
+    /// en passant square, halfmove clock and fullmove number.
+    /// The two clocks may be left out, in which case they default to 0 and 1
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chess_base::board::Board;
+    ///
+    /// let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+    /// assert!(board.is_ok());
+    /// ```
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let fields = split_fields(fen);
+        let end = fen.chars().count();
+
+        let field = |index: usize, field_type: FenField| {
+            fields
+                .get(index)
+                .map(|(offset, field)| (*offset, field.as_str()))
+                .ok_or(FenError::new(field_type, end, FenErrorKind::MissingField))
+        };
+
+        let (offset, placement) = field(0, FenField::PiecePlacement)?;
+        let pieces = parse_placement(offset, placement)?;
+
+        let (offset, turn) = field(1, FenField::SideToMove)?;
+        let turn = parse_turn(offset, turn)?;
+
+        let mut board = Board::new();
+        for piece in &pieces {
+            board.add_piece(piece.get_position(), *piece);
+        }
+        if board.is_in_check(turn.opponent()) {
+            return Err(FenError::new(
+                FenField::SideToMove,
+                offset,
+                FenErrorKind::OpponentInCheck,
+            ));
+        }
+
+        let mut modifiers = Modifiers::new();
+
+        let (offset, castling) = field(2, FenField::Castling)?;
+        parse_castling(offset, castling, &mut modifiers)?;
+
+        let (offset, en_passant) = field(3, FenField::EnPassant)?;
+        modifiers.en_passant = parse_en_passant(offset, en_passant, turn, &pieces)?;
+
+        if let Some((offset, halfmove_clock)) = fields.get(4) {
+            modifiers.halfmove_clock =
+                parse_number(FenField::HalfmoveClock, *offset, halfmove_clock)?;
+        }
+
+        if let Some((offset, fullmove_number)) = fields.get(5) {
+            modifiers.fullmove_number =
+                parse_number(FenField::FullmoveNumber, *offset, fullmove_number)?;
+            if modifiers.fullmove_number == 0 {
+                return Err(FenError::new(
+                    FenField::FullmoveNumber,
+                    *offset,
+                    FenErrorKind::InvalidNumber,
+                ));
+            }
+        }
+
+        if let Some((offset, _)) = fields.get(6) {
+            return Err(FenError::new(
+                FenField::FullmoveNumber,
+                *offset,
+                FenErrorKind::TrailingInput,
+            ));
+        }
+
+        board.hash ^= board.state_hash();
+        board.modifiers = modifiers;
+        board.turn = turn;
+        board.hash ^= board.state_hash();
+
+        Ok(board)
+    }
+
+    /// Returns the position in FEN notation
+    /// The ranks are written from the eighth down to the first, followed by the
+    /// side to move, castling rights, en passant square, halfmove clock and fullmove number,
+    /// so the result can be read back with Board::from_fen or by any other chess tool
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in (0..=7).rev() {
+            let mut empty = 0;
+            for file in 0..=7 {
+                if let Some(piece) = self.get_piece(Position::new(file, rank)) {
+                    if empty > 0 {
+                        fen.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    fen.push(piece_to_char(piece));
+                } else {
+                    empty += 1;
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank > 0 {
+                fen.push('/');
+            }
+        }
+
+        fen.push(' ');
+        fen.push(match self.turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        fen.push(' ');
+        let castling_start = fen.len();
+        let rights = self.modifiers.castling_rights;
+        for (c, color, castle_type) in [
+            ('K', Color::White, Castles::KingSide),
+            ('Q', Color::White, Castles::QueenSide),
+            ('k', Color::Black, Castles::KingSide),
+            ('q', Color::Black, Castles::QueenSide),
+        ] {
+            if rights.has(color, castle_type) {
+                fen.push(c);
+            }
+        }
+        if fen.len() == castling_start {
+            fen.push('-');
+        }
+
+        fen.push(' ');
+        match self.modifiers.en_passant {
+            Some(position) => fen.push_str(&position.to_string()),
+            None => fen.push('-'),
+        }
+
+        fen.push_str(&format!(
+            " {} {}",
+            self.modifiers.halfmove_clock, self.modifiers.fullmove_number
+        ));
+
+        fen
+    }
+}
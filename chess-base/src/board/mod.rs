@@ -1,45 +1,73 @@
 /// Board module
 /// Contains the code for the board struct
 /// Contains most of the high level game logic accessible for the user
+mod fen;
 mod modifiers;
-use crate::core::castles::Castles::{self, KingSide, QueenSide};
+mod outcome;
+mod perft;
+mod see;
+mod status;
+mod undo;
+mod zobrist;
+use crate::core::attacks;
+use crate::core::bitboard::{Bitboards, Squares};
+use crate::core::castles::Castles::{KingSide, QueenSide};
 use crate::core::color::Color;
 use crate::core::color::Color::Black;
 use crate::core::color::Color::White;
 use crate::core::direction::Direction;
-use crate::core::direction::Direction::{Down, Up};
 use crate::core::mov::Move;
 use crate::core::piece::Piece;
 use crate::core::pieces::Pieces;
 use crate::core::position::Position;
+pub use fen::{FenError, FenErrorKind, FenField};
 use modifiers::Modifiers;
+pub use outcome::{MoveError, MoveOutcome};
+pub use status::{DrawReason, GameStatus};
 use std::collections::HashMap;
+pub use undo::UndoInfo;
 
 /// Board struct
-/// Contains a hashmap of all the pieces on the board
-/// Contains a string representation of the board in FEN notation
+/// Keeps the pieces in bitboards, one for every piece type and color,
+/// next to an array indexed by square for quick lookups of the piece on a square
 #[derive(Debug, Clone)]
 pub struct Board {
-    pieces: HashMap<Position, Piece>,
+    squares: [Option<Piece>; 64],
+    bitboards: Bitboards,
     modifiers: Modifiers,
     turn: Color,
-    fen: String,
+    hash: u64,
+    /// The hashes of the positions before every move made on the board, oldest first
+    history: Vec<u64>,
+    /// Set when the game ended by resignation or a claimed draw
+    ending: Option<GameStatus>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Board::new()
+    }
 }
 
 impl Board {
     /// Creates a new empty board
     pub fn new() -> Board {
-        Board {
-            pieces: HashMap::new(),
+        let mut board = Board {
+            squares: [None; 64],
+            bitboards: Bitboards::new(),
             modifiers: Modifiers::new(),
             turn: White,
-            fen: String::new(),
-        }
+            hash: 0,
+            history: Vec::new(),
+            ending: None,
+        };
+        board.hash = board.state_hash();
+        board
     }
 
     /// Creates a new board with all the pieces in their starting positions
     pub fn new_arranged() -> Board {
-        let mut pieces = HashMap::new();
+        let mut board = Board::new();
         for rank in 0..=7 {
             for file in 0..=7 {
                 let position = Position::new(file, rank);
@@ -65,203 +93,108 @@ impl Board {
                     _ => None,
                 };
                 if let Some(piece) = piece {
-                    pieces.insert(position, piece);
+                    board.add_piece(position, piece);
                 }
             }
         }
-        Board {
-            pieces,
-            modifiers: Modifiers::new(),
-            turn: White,
-            fen: String::new(),
-        }
+        board
     }
 
-    pub fn from_fen(fen: &str) -> Board {
-        let mut pieces = HashMap::new();
-        let modifiers = Modifiers::new();
-        let turn = Color::White;
-        let mut rank = 7;
-        let mut file = 0;
-
-        for c in fen.chars() {
-            match c {
-                ' ' => break,
-                '/' => {
-                    rank -= 1;
-                    file = 0;
-                }
-                'K' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::King),
-                    );
-                    file += 1;
-                }
-                'Q' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::Queen),
-                    );
-                    file += 1;
-                }
-                'R' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::Rook),
-                    );
-                    file += 1;
-                }
-                'B' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::Bishop),
-                    );
-                    file += 1;
-                }
-                'N' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::Knight),
-                    );
-                    file += 1;
-                }
-                'P' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::White, Pieces::Pawn),
-                    );
-                    file += 1;
-                }
-                'k' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::King),
-                    );
-                    file += 1;
-                }
-                'q' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::Queen),
-                    );
-                    file += 1;
-                }
-                'r' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::Rook),
-                    );
-                    file += 1;
-                }
-                'b' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::Bishop),
-                    );
-                    file += 1;
-                }
-                'n' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::Knight),
-                    );
-                    file += 1;
-                }
-                'p' => {
-                    pieces.insert(
-                        Position::new(file, rank),
-                        Piece::new(Position::new(file, rank), Color::Black, Pieces::Pawn),
-                    );
-                    file += 1;
-                }
-                '1'..='8' => {
-                    let count = c.to_digit(10).unwrap() as usize;
-                    for _ in 0..count {
-                        pieces.remove(&Position::new(file, rank));
-                        file += 1;
-                    }
-                }
-                _ => (),
-            }
-        }
-
-        Board {
-            pieces,
-            modifiers,
-            turn,
-            fen: fen.to_string(),
+    /// Returns a reference to the piece at the given position
+    pub fn get_piece(&self, position: Position) -> Option<&Piece> {
+        if position.get_x() > 7 || position.get_y() > 7 {
+            return None;
         }
+        self.squares[position.to_index()].as_ref()
     }
 
-    /// Returns a reference to the piece at the given position
-    pub fn get_piece(&self, position: Position) -> Option<&Piece> {
-        self.pieces.get(&position)
+    /// Returns a map of all the pieces on the board by their position
+    /// The map is built on every call, prefer get_piece or get_bitboards where possible
+    pub fn get_all_pieces(&self) -> HashMap<Position, Piece> {
+        self.squares
+            .iter()
+            .flatten()
+            .map(|piece| (piece.get_position(), *piece))
+            .collect()
     }
 
-    pub fn get_all_pieces(&self) -> &HashMap<Position, Piece> {
-        &self.pieces
+    /// Returns the bitboards of all the pieces on the board
+    pub fn get_bitboards(&self) -> &Bitboards {
+        &self.bitboards
     }
 
     pub fn get_turn(&self) -> Color {
         self.turn
     }
 
-    pub fn get_fen(&self) -> String {
-        self.fen.clone()
-    }
-
     pub fn get_modifiers(&self) -> &Modifiers {
         &self.modifiers
     }
 
+    /// Returns the Zobrist hash of the position
+    /// It covers the pieces, the side to move, the castling rights and the en passant file,
+    /// and is the same as the key the Polyglot opening book format uses for the position
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Takes the piece at the given position off the board and returns it,
+    /// None if the position is empty or off the board
     pub fn remove_piece(&mut self, position: Position) -> Option<Piece> {
-        self.pieces.remove(&position)
+        if position.get_x() > 7 || position.get_y() > 7 {
+            return None;
+        }
+        let index = position.to_index();
+        let piece = self.squares[index].take()?;
+        self.bitboards
+            .remove(index, piece.get_color(), piece.get_piece_type());
+        self.hash ^= zobrist::piece_key(index, &piece);
+        Some(piece)
     }
 
-    pub fn add_piece(&mut self, position: Position, piece: Piece) {
-        self.pieces.insert(position, piece);
+    /// Puts a piece on the board, replacing the piece that was on that position
+    /// Does nothing if the position is off the board
+    pub fn add_piece(&mut self, position: Position, mut piece: Piece) {
+        if position.get_x() > 7 || position.get_y() > 7 {
+            return;
+        }
+        self.remove_piece(position);
+
+        let index = position.to_index();
+        piece.set_position(position);
+        self.bitboards
+            .add(index, piece.get_color(), piece.get_piece_type());
+        self.hash ^= zobrist::piece_key(index, &piece);
+        self.squares[index] = Some(piece);
     }
 
-    pub fn generate_fen(&mut self) -> String {
-        let mut fen = String::new();
-        for rank in 0..=7 {
-            let mut empty = 0;
-            for file in 0..=7 {
-                let position = Position::new(file, rank);
-                if let Some(piece) = self.get_piece(position) {
-                    if empty > 0 {
-                        fen.push_str(&empty.to_string());
-                        empty = 0;
-                    }
-                    match piece.get_color() {
-                        White => match piece.get_piece_type() {
-                            Pieces::Pawn => fen.push_str("P"),
-                            Pieces::Rook => fen.push_str("R"),
-                            Pieces::Knight => fen.push_str("N"),
-                            Pieces::Bishop => fen.push_str("B"),
-                            Pieces::Queen => fen.push_str("Q"),
-                            Pieces::King => fen.push_str("K"),
-                        },
-                        Black => match piece.get_piece_type() {
-                            Pieces::Pawn => fen.push_str("p"),
-                            Pieces::Rook => fen.push_str("r"),
-                            Pieces::Knight => fen.push_str("n"),
-                            Pieces::Bishop => fen.push_str("b"),
-                            Pieces::Queen => fen.push_str("q"),
-                            Pieces::King => fen.push_str("k"),
-                        },
-                    }
-                } else {
-                    empty += 1;
-                }
-            }
-            if empty > 0 {
-                fen.push_str(&empty.to_string());
+    /// Returns the part of the hash that does not come from the pieces:
+    /// the castling rights, the en passant file and the side to move.
+    /// Like in Polyglot the en passant file only counts when a pawn can actually capture there
+    fn state_hash(&self) -> u64 {
+        let mut hash = 0;
+
+        for right in Squares(self.modifiers.castling_rights.bits() as u64) {
+            hash ^= zobrist::castling_key(right);
+        }
+
+        if let Some(en_passant) = self.modifiers.en_passant {
+            let opponent = match self.turn {
+                White => Black,
+                Black => White,
+            };
+            let capturers = attacks::pawn_attacks(en_passant.to_index(), opponent)
+                & self.bitboards.pieces(self.turn, Pieces::Pawn);
+            if capturers != 0 {
+                hash ^= zobrist::en_passant_key(en_passant.get_x());
             }
-            fen.push_str("/");
         }
-        fen
+
+        if self.turn == White {
+            hash ^= zobrist::turn_key();
+        }
+
+        hash
     }
 
     pub fn get_all_legal_moves_for_black(&self) -> Vec<Move> {
@@ -280,664 +213,644 @@ impl Board {
     }
 
     pub fn is_in_check(&self, color: Color) -> bool {
-        let mut king_pos = None;
-        for piece in self.get_all_pieces().values() {
-            if piece.get_piece_type() == Pieces::King && piece.get_color() == color {
-                king_pos = Some(piece.get_position());
-                break;
-            }
-        }
+        let kings = self.bitboards.pieces(color, Pieces::King);
 
-        if let None = king_pos {
+        if kings == 0 {
             return false;
         }
 
-        let king_pos = king_pos.unwrap();
+        let king_pos = Position::from_index(kings.trailing_zeros() as usize);
+        let opponent = match color {
+            White => Black,
+            Black => White,
+        };
+
+        self.is_attacked(king_pos, opponent)
+    }
+
+    /// Returns true if a piece of the given color attacks the position
+    pub fn is_attacked(&self, position: Position, by: Color) -> bool {
+        let index = position.to_index();
+        let bitboards = &self.bitboards;
+        let occupancy = bitboards.all();
+        let queens = bitboards.pieces(by, Pieces::Queen);
+        let defender = match by {
+            White => Black,
+            Black => White,
+        };
+
+        attacks::rook_attacks(index, occupancy) & (bitboards.pieces(by, Pieces::Rook) | queens)
+            != 0
+            || attacks::bishop_attacks(index, occupancy)
+                & (bitboards.pieces(by, Pieces::Bishop) | queens)
+                != 0
+            || attacks::knight_attacks(index) & bitboards.pieces(by, Pieces::Knight) != 0
+            || attacks::king_attacks(index) & bitboards.pieces(by, Pieces::King) != 0
+            // a pawn attacks the square if a pawn on the square would attack the pawn
+            || attacks::pawn_attacks(index, defender) & bitboards.pieces(by, Pieces::Pawn) != 0
+    }
 
-        let mut in_check = false;
+    /// Returns the legal moves of one color
+    pub fn get_all_legal_moves_for(&self, color: Color) -> Vec<Move> {
         match color {
-            White => {
-                for mov in self.get_all_pseudo_legal_moves_for_black() {
-                    match mov {
-                        Move::Normal { from: _, to } => {
-                            if to == king_pos {
-                                in_check = true;
-                            }
-                        }
-                        _ => (),
-                    }
-                }
-            }
-            Black => {
-                for mov in self.get_all_pseudo_legal_moves_for_white() {
-                    match mov {
-                        Move::Normal { from: _, to } => {
-                            if to == king_pos {
-                                in_check = true;
-                            }
-                        }
-                        _ => (),
-                    }
-                }
-            }
+            White => self.get_all_legal_moves_for_white(),
+            Black => self.get_all_legal_moves_for_black(),
         }
+    }
 
-        in_check
+    /// Returns true if the color is in check and has no legal moves
+    pub fn in_checkmate(&self, color: Color) -> bool {
+        self.is_in_check(color) && self.get_all_legal_moves_for(color).is_empty()
     }
 
-    pub fn calculate_nr_of_moves_with_depth(&self, depth: u32) -> u32 {
-        let mut nr_of_moves = 0;
-        if depth == 0 {
-            return 1;
+    /// Returns true if the color is not in check but has no legal moves
+    pub fn in_stalemate(&self, color: Color) -> bool {
+        !self.is_in_check(color) && self.get_all_legal_moves_for(color).is_empty()
+    }
+
+    /// Returns the state of the game: whether it is still going on, and if not how it ended
+    /// The legal moves are only generated once, so prefer this over calling
+    /// in_checkmate, in_stalemate and the draw checks one by one
+    pub fn status(&self) -> GameStatus {
+        if let Some(ending) = self.ending {
+            return ending;
         }
 
-        match self.get_turn() {
-            White => {
-                for mov in self.get_all_legal_moves_for_white() {
-                    let mut board = self.clone();
-                    board.make_move(mov);
-                    nr_of_moves += board.calculate_nr_of_moves_with_depth(depth - 1);
-                }
-            }
-            Black => {
-                for mov in self.get_all_legal_moves_for_black() {
-                    let mut board = self.clone();
-                    board.make_move(mov);
-                    nr_of_moves += board.calculate_nr_of_moves_with_depth(depth - 1);
-                }
+        if self.get_all_legal_moves_for(self.turn).is_empty() {
+            if self.is_in_check(self.turn) {
+                return GameStatus::Checkmate {
+                    winner: self.turn.opponent(),
+                };
             }
+            return GameStatus::Stalemate;
         }
 
-        nr_of_moves
+        // checkmate goes before the automatic draws, and there is none at this point
+        if self.is_fivefold_repetition() {
+            GameStatus::Draw(DrawReason::FivefoldRepetition)
+        } else if self.modifiers.halfmove_clock >= 150 {
+            GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+        } else if self.is_insufficient_material() {
+            GameStatus::Draw(DrawReason::InsufficientMaterial)
+        } else {
+            GameStatus::Ongoing
+        }
     }
 
-    pub fn make_move_no_checks(&mut self, mov: Move) {
-        match mov {
-            Move::Normal { from, to } => {
-                let piece = self.remove_piece(from).unwrap();
-                self.remove_piece(to);
-                self.add_piece(to, piece);
-            }
-            Move::Promotion {
-                from,
-                to,
-                promotion,
-            } => {
-                let piece = self.remove_piece(from).unwrap();
-                self.remove_piece(to);
-                self.add_piece(to, Piece::new(to, piece.get_color(), promotion));
-            }
-            Move::Castle { color, castle_type } => match color {
-                White => match castle_type {
-                    Castles::KingSide => {
-                        let king = self.remove_piece(Position::new(4, 0)).unwrap();
-                        let rook = self.remove_piece(Position::new(7, 0)).unwrap();
-                        self.add_piece(Position::new(6, 0), king);
-                        self.add_piece(Position::new(5, 0), rook);
-                    }
-                    Castles::QueenSide => {
-                        let king = self.remove_piece(Position::new(4, 0)).unwrap();
-                        let rook = self.remove_piece(Position::new(0, 0)).unwrap();
-                        self.add_piece(Position::new(2, 0), king);
-                        self.add_piece(Position::new(3, 0), rook);
-                    }
-                },
-                Black => match castle_type {
-                    Castles::KingSide => {
-                        let king = self.remove_piece(Position::new(4, 7)).unwrap();
-                        let rook = self.remove_piece(Position::new(7, 7)).unwrap();
-                        self.add_piece(Position::new(6, 7), king);
-                        self.add_piece(Position::new(5, 7), rook);
-                    }
-                    Castles::QueenSide => {
-                        let king = self.remove_piece(Position::new(4, 7)).unwrap();
-                        let rook = self.remove_piece(Position::new(0, 7)).unwrap();
-                        self.add_piece(Position::new(2, 7), king);
-                        self.add_piece(Position::new(3, 7), rook);
-                    }
-                },
-            },
+    /// Ends the game with a win for the opponent of the color
+    /// Does nothing if the game is already over
+    pub fn resign(&mut self, color: Color) {
+        if !self.status().is_over() {
+            self.ending = Some(GameStatus::Resigned {
+                winner: color.opponent(),
+            });
         }
     }
 
-    pub fn in_checkmate(&self, color: Color) -> bool {
-        match color {
-            White => {
-                let pseudo_moves = self.get_all_legal_moves_for_white();
-                let moves = self.verify_checks(pseudo_moves);
-                if moves.len() == 0 && self.is_in_check(color) {
-                    return true;
+    /// Ends the game in a draw if the side to move is allowed to claim one,
+    /// because of a threefold repetition or the fifty move rule
+    /// Returns the reason of the draw, or None if no draw can be claimed
+    pub fn claim_draw(&mut self) -> Option<DrawReason> {
+        if self.status().is_over() {
+            return None;
+        }
+
+        let reason = if self.is_threefold_repetition() {
+            DrawReason::ThreefoldRepetition
+        } else if self.can_claim_fifty_move_draw() {
+            DrawReason::FiftyMoveRule
+        } else {
+            return None;
+        };
+
+        self.ending = Some(GameStatus::Draw(reason));
+        Some(reason)
+    }
+
+    /// Returns how many times the current position has occurred in the game, this time included
+    /// Positions count as the same when the same side is to move, the pieces stand on the same
+    /// squares and the castling rights and the possible en passant captures are the same,
+    /// which is exactly what the hash covers
+    pub fn repetition_count(&self) -> usize {
+        // positions from before the last capture or pawn move can not come back,
+        // and only the positions with the same side to move can be the same
+        1 + self
+            .history
+            .iter()
+            .rev()
+            .take(self.modifiers.halfmove_clock as usize)
+            .skip(1)
+            .step_by(2)
+            .filter(|hash| **hash == self.hash)
+            .count()
+    }
+
+    /// Returns true if the current position occurred at least three times,
+    /// which allows the side to move to claim a draw
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    /// Returns true if the current position occurred at least five times,
+    /// which ends the game in a draw without either side having to claim it
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetition_count() >= 5
+    }
+
+    /// Returns true if fifty moves by each side were made without a capture or a pawn move,
+    /// which allows the side to move to claim a draw
+    pub fn can_claim_fifty_move_draw(&self) -> bool {
+        self.modifiers.halfmove_clock >= 100
+    }
+
+    /// Returns true if seventy-five moves by each side were made without a capture or a pawn move,
+    /// which ends the game in a draw without either side having to claim it,
+    /// unless the last move was checkmate
+    pub fn is_seventy_five_move_draw(&self) -> bool {
+        self.modifiers.halfmove_clock >= 150 && !self.in_checkmate(self.turn)
+    }
+
+    /// Returns true if neither side has enough material left to checkmate,
+    /// which ends the game in a draw. That is the case when there are no pawns, rooks or queens
+    /// and at most one knight or bishop, or only bishops that all stand on squares of one color
+    pub fn is_insufficient_material(&self) -> bool {
+        let bitboards = &self.bitboards;
+        let pieces =
+            |piece_type| bitboards.pieces(White, piece_type) | bitboards.pieces(Black, piece_type);
+
+        if pieces(Pieces::Pawn) | pieces(Pieces::Rook) | pieces(Pieces::Queen) != 0 {
+            return false;
+        }
+
+        let knights = pieces(Pieces::Knight);
+        let bishops = pieces(Pieces::Bishop);
+        if (knights | bishops).count_ones() <= 1 {
+            return true;
+        }
+
+        // bishops on one color of squares can never attack the other color,
+        // so the king can always escape to a square they can not reach
+        const LIGHT_SQUARES: u64 = 0x55aa_55aa_55aa_55aa;
+        knights == 0 && (bishops & LIGHT_SQUARES == 0 || bishops & !LIGHT_SQUARES == 0)
+    }
+
+    /// Makes a move on the board if it is legal for the side to move
+    /// On success the effects of the move are returned,
+    /// otherwise the board is left untouched and the reason the move was rejected is returned
+    pub fn make_move(&mut self, mov: Move) -> Result<MoveOutcome, MoveError> {
+        let mov = self.resolve_move(mov);
+        self.check_move(mov)?;
+
+        let is_en_passant = matches!(mov, Move::EnPassant { .. });
+        let undo = self.make_move_no_checks(mov);
+
+        let is_check = self.is_in_check(self.turn);
+        let is_checkmate = is_check && self.in_checkmate(self.turn);
+
+        Ok(MoveOutcome {
+            captured: undo.get_captured().map(|piece| piece.get_piece_type()),
+            is_check,
+            is_checkmate,
+            is_en_passant,
+            undo,
+        })
+    }
+
+    /// Turns a normal move that only gives the squares into the move it stands for on this board:
+    /// a king moving two squares from its starting square castles,
+    /// and a pawn moving diagonally onto the en passant square captures en passant.
+    /// Other moves are returned as they are
+    pub fn resolve_move(&self, mov: Move) -> Move {
+        let Move::Normal { from, to } = mov else {
+            return mov;
+        };
+        let Some(piece) = self.get_piece(from) else {
+            return mov;
+        };
+
+        match piece.get_piece_type() {
+            Pieces::King => {
+                let rank = match piece.get_color() {
+                    White => 0,
+                    Black => 7,
+                };
+                let castle_type = match to.get_x() {
+                    6 => KingSide,
+                    2 => QueenSide,
+                    _ => return mov,
+                };
+
+                if from == Position::new(4, rank) && to.get_y() == rank {
+                    return Move::Castle {
+                        color: piece.get_color(),
+                        castle_type,
+                    };
                 }
             }
-            Black => {
-                let pseudo_moves = self.get_all_legal_moves_for_black();
-                let moves = self.verify_checks(pseudo_moves);
-                if moves.len() == 0 && self.is_in_check(color) {
-                    return true;
-                }
+            Pieces::Pawn if from.get_x() != to.get_x() && self.modifiers.en_passant == Some(to) => {
+                return Move::EnPassant { from, to };
             }
+            _ => (),
         }
 
-        false
+        mov
     }
 
-    pub fn in_stalemate(&self, color: Color) -> bool {
-        match color {
-            White => {
-                let pseudo_moves = self.get_all_legal_moves_for_white();
-                let moves = self.verify_checks(pseudo_moves);
-                if moves.len() == 0 {
-                    return true;
+    /// Returns the reason the move can not be made by the side to move, if there is one
+    fn check_move(&self, mov: Move) -> Result<(), MoveError> {
+        // a game without legal moves is over as well, but then there is no move to accept anyway
+        if self.ending.is_some()
+            || self.is_fivefold_repetition()
+            || self.is_seventy_five_move_draw()
+            || self.is_insufficient_material()
+        {
+            return Err(MoveError::GameOver);
+        }
+
+        let (from, to) = match mov {
+            Move::Normal { from, to } => (from, to),
+            Move::Promotion { from, to, .. } => (from, to),
+            Move::EnPassant { from, to } => (from, to),
+            Move::Castle { color, castle_type } => {
+                if color != self.turn {
+                    return Err(MoveError::NotYourTurn(color));
                 }
-            }
-            Black => {
-                let pseudo_moves = self.get_all_legal_moves_for_black();
-                let moves = self.verify_checks(pseudo_moves);
-                if moves.len() == 0 {
-                    return true;
+
+                let mut moves = vec![];
+                for index in Squares(self.bitboards.pieces(color, Pieces::King)) {
+                    moves = self.squares[index].unwrap().get_all_legal_moves(self);
+                }
+
+                if !self.verify_checks(moves).contains(&mov) {
+                    return Err(MoveError::CastlingNotAllowed(castle_type));
                 }
+
+                return Ok(());
+            }
+        };
+
+        for position in [from, to] {
+            if position.get_x() > 7 || position.get_y() > 7 {
+                return Err(MoveError::OffBoard(position));
             }
         }
 
-        false
-    }
+        let piece = self.get_piece(from).ok_or(MoveError::NoPiece(from))?;
+
+        if piece.get_color() != self.turn {
+            return Err(MoveError::OpponentPiece(from));
+        }
+
+        let reaches_last_rank =
+            piece.get_piece_type() == Pieces::Pawn && (to.get_y() == 0 || to.get_y() == 7);
 
-    pub fn make_move(&mut self, mov: Move) -> bool {
         match mov {
-            Move::Normal { from, to } => {
-                if from.get_x() > 7 || from.get_y() > 7 || to.get_x() > 7 || to.get_y() > 7 {
-                    //warn!("Invalid location at from {} and/or to {}", from, to);
-                    return false;
+            Move::Normal { .. } if reaches_last_rank => return Err(MoveError::MissingPromotion),
+            Move::Promotion { promotion, .. } => {
+                if !reaches_last_rank {
+                    return Err(MoveError::NotAPromotion);
                 }
-
-                if let None = self.get_piece(from) {
-                    //warn!("No piece at {}", from);
-                    return false;
+                if promotion == Pieces::Pawn || promotion == Pieces::King {
+                    return Err(MoveError::InvalidPromotionPiece(promotion));
                 }
+            }
+            _ => (),
+        }
 
-                if let Some(piece) = self.get_piece(from) {
-                    if piece.get_color() != self.turn {
-                        //warn!("Wrong color piece at {}", from);
-                        return false;
-                    }
-                }
+        let moves = piece.get_all_legal_moves(self);
 
-                let moves = self.get_piece(from).unwrap().get_all_legal_moves(self);
-
-                // DEBUG
-                // debug!("!!PSEUDO LEGAL MOVES:");
-                // for mv in moves.as_slice() {
-                //     match mv {
-                //         Move::Normal { from, to } => {
-                //             debug!("Legal move from {} to {}", from, to);
-                //         }
-                //         _ => (),
-                //     }
-                // }
-                // END DEBUG
-
-                let moves = self.verify_checks(moves);
-
-                if moves.contains(&mov) {
-                    //info!("Making move from {} to {}", from, to);
-
-                    self.pieces.remove(&to);
-                    let mut piece = self.pieces.remove(&from).unwrap();
-                    piece.set_position(to);
-
-                    let to_x = to.get_x();
-                    let to_y = to.get_y();
-
-                    match to_x {
-                        0 => {
-                            if to_y == 0 {
-                                self.modifiers.can_white_castle_queenside = false;
-                            } else if to_y == 7 {
-                                self.modifiers.can_black_castle_queenside = false;
-                            }
-                        }
-                        7 => {
-                            if to_y == 0 {
-                                self.modifiers.can_white_castle_kingside = false;
-                            } else if to_y == 7 {
-                                self.modifiers.can_black_castle_kingside = false;
-                            }
-                        }
-                        _ => (),
-                    }
+        if !moves.contains(&mov) {
+            return Err(MoveError::InvalidMove(from, to));
+        }
 
-                    if piece.get_piece_type() == Pieces::King {
-                        match self.turn {
-                            White => {
-                                self.modifiers.can_white_castle_kingside = false;
-                                self.modifiers.can_white_castle_queenside = false;
-                            }
-                            Black => {
-                                self.modifiers.can_black_castle_kingside = false;
-                                self.modifiers.can_black_castle_queenside = false;
-                            }
-                        }
-                    } else if piece.get_piece_type() == Pieces::Rook {
-                        match self.turn {
-                            White => {
-                                if from == Position::new(0, 0) {
-                                    self.modifiers.can_white_castle_queenside = false;
-                                } else if from == Position::new(7, 0) {
-                                    self.modifiers.can_white_castle_kingside = false;
-                                }
-                            }
-                            Black => {
-                                if from == Position::new(0, 7) {
-                                    self.modifiers.can_black_castle_queenside = false;
-                                } else if from == Position::new(7, 7) {
-                                    self.modifiers.can_black_castle_kingside = false;
-                                }
-                            }
-                        }
-                    } else if piece.get_piece_type() == Pieces::Pawn {
-                        match piece.get_color() {
-                            White => {
-                                if let Some(ep) = self.modifiers.en_passant {
-                                    if ep == to {
-                                        self.pieces.remove(
-                                            &piece.get_position().increment(Up, 1).unwrap(),
-                                        );
-                                    }
-
-                                    self.modifiers.en_passant = None;
-                                }
-                            }
-                            Black => {
-                                if let Some(ep) = self.modifiers.en_passant {
-                                    if ep == to {
-                                        self.pieces.remove(
-                                            &piece.get_position().increment(Down, 1).unwrap(),
-                                        );
-                                    }
-                                }
-
-                                self.modifiers.en_passant = None;
-                            }
-                        }
+        if self.verify_checks(vec![mov]).is_empty() {
+            return Err(MoveError::LeavesKingInCheck);
+        }
 
-                        match self.turn {
-                            White => {
-                                if from.get_y() == 1 && to.get_y() == 3 {
-                                    self.modifiers.en_passant =
-                                        Some(to.increment(Direction::Up, 1).unwrap());
-                                }
+        Ok(())
+    }
+
+    /// Moves the pieces and updates the castling rights, the en passant square, the clocks and the turn
+    /// The move is expected to be legal, it is not checked
+    /// Returns the record needed to take the move back with unmake_move
+    pub fn make_move_no_checks(&mut self, mov: Move) -> UndoInfo {
+        let modifiers = self.modifiers;
+        let hash = self.hash;
+        // the pieces update the hash as they move, the rest is swapped out as a whole
+        self.hash ^= self.state_hash();
+        self.modifiers.en_passant = None;
+        let mut captured = None;
+
+        match mov {
+            Move::Normal { from, to } => {
+                captured = self.remove_piece(to);
+                let mut piece = self.remove_piece(from).unwrap();
+                piece.set_position(to);
+
+                // a king that moves loses both rights, even when it was not on its starting square
+                self.modifiers
+                    .castling_rights
+                    .update(from.to_index(), to.to_index());
+                if piece.get_piece_type() == Pieces::King {
+                    self.modifiers
+                        .castling_rights
+                        .remove_color(piece.get_color());
+                } else if piece.get_piece_type() == Pieces::Pawn {
+                    match piece.get_color() {
+                        White => {
+                            if from.get_y() == 1 && to.get_y() == 3 {
+                                self.modifiers.en_passant =
+                                    Some(to.increment(Direction::Up, 1).unwrap());
                             }
-                            Black => {
-                                if from.get_y() == 6 && to.get_y() == 4 {
-                                    self.modifiers.en_passant =
-                                        Some(to.increment(Direction::Down, 1).unwrap());
-                                }
+                        }
+                        Black => {
+                            if from.get_y() == 6 && to.get_y() == 4 {
+                                self.modifiers.en_passant =
+                                    Some(to.increment(Direction::Down, 1).unwrap());
                             }
                         }
                     }
-
-                    self.pieces.insert(to, piece);
-                } else {
-                    //warn!("Illegal move from {} to {}", from, to);
-                    return false;
                 }
+
+                self.add_piece(to, piece);
+            }
+            Move::EnPassant { from, to } => {
+                // the captured pawn stands on the rank the capturing pawn comes from,
+                // anything else that stands there is left alone
+                let pawn = self.remove_piece(from).unwrap();
+                let square = Position::new(to.get_x(), from.get_y());
+                if self.get_piece(square).is_some_and(|piece| {
+                    piece.get_piece_type() == Pieces::Pawn && piece.get_color() != pawn.get_color()
+                }) {
+                    captured = self.remove_piece(square);
+                }
+                self.add_piece(to, pawn);
             }
             Move::Castle { color, castle_type } => {
-                let mut moves = vec![];
-                for piece in self.pieces.values() {
-                    if piece.get_color() == color && piece.get_piece_type() == Pieces::King {
-                        moves = piece.get_all_legal_moves(self);
-                    }
-                }
+                let (king_to, rook_from, rook_to) = match castle_type {
+                    KingSide => (6, 7, 5),
+                    QueenSide => (2, 0, 3),
+                };
+                let rank = match color {
+                    White => 0,
+                    Black => 7,
+                };
 
-                let moves = self.verify_checks(moves);
-
-                if moves.contains(&mov) {
-                    match castle_type {
-                        KingSide => {
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
-                            let king_to = match color {
-                                White => Position::new(6, 0),
-                                Black => Position::new(6, 7),
-                            };
-                            let rook_from = match color {
-                                White => Position::new(7, 0),
-                                Black => Position::new(7, 7),
-                            };
-                            let rook_to = match color {
-                                White => Position::new(5, 0),
-                                Black => Position::new(5, 7),
-                            };
-
-                            let mut king = self.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            self.pieces.insert(king_to, king);
-
-                            let mut rook = self.pieces.remove(&rook_from).unwrap();
-                            rook.set_position(rook_to);
-                            self.pieces.insert(rook_to, rook);
-                        }
-                        QueenSide => {
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
-                            let king_to = match color {
-                                White => Position::new(2, 0),
-                                Black => Position::new(2, 7),
-                            };
-                            let rook_from = match color {
-                                White => Position::new(0, 0),
-                                Black => Position::new(0, 7),
-                            };
-                            let rook_to = match color {
-                                White => Position::new(3, 0),
-                                Black => Position::new(3, 7),
-                            };
-
-                            let mut king = self.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            self.pieces.insert(king_to, king);
-
-                            let mut rook = self.pieces.remove(&rook_from).unwrap();
-                            rook.set_position(rook_to);
-                            self.pieces.insert(rook_to, rook);
-                        }
-                    }
-                } else {
-                    //warn!("Illegal castleing move");
-                    return false;
-                }
+                let mut king = self.remove_piece(Position::new(4, rank)).unwrap();
+                king.set_position(Position::new(king_to, rank));
+                self.add_piece(Position::new(king_to, rank), king);
 
-                match self.turn {
-                    White => {
-                        self.modifiers.can_white_castle_kingside = false;
-                        self.modifiers.can_white_castle_queenside = false;
-                    }
-                    Black => {
-                        self.modifiers.can_black_castle_kingside = false;
-                        self.modifiers.can_black_castle_queenside = false;
-                    }
-                }
+                let mut rook = self.remove_piece(Position::new(rook_from, rank)).unwrap();
+                rook.set_position(Position::new(rook_to, rank));
+                self.add_piece(Position::new(rook_to, rank), rook);
+
+                self.modifiers.castling_rights.remove_color(color);
             }
             Move::Promotion {
                 from,
                 to,
                 promotion,
             } => {
-                let mut moves = vec![];
-                for piece in self.pieces.values() {
-                    if piece.get_position() == from {
-                        moves = piece.get_all_legal_moves(self);
-                    }
-                }
-
-                let moves = self.verify_checks(moves);
-
-                if moves.contains(&mov) {
-                    let to_x = to.get_x();
-                    let to_y = to.get_y();
-
-                    match to_x {
-                        0 => {
-                            if to_y == 0 {
-                                self.modifiers.can_white_castle_queenside = false;
-                            } else if to_y == 7 {
-                                self.modifiers.can_black_castle_queenside = false;
-                            }
-                        }
-                        7 => {
-                            if to_y == 0 {
-                                self.modifiers.can_white_castle_kingside = false;
-                            } else if to_y == 7 {
-                                self.modifiers.can_black_castle_kingside = false;
-                            }
-                        }
-                        _ => (),
-                    }
+                self.modifiers
+                    .castling_rights
+                    .update(from.to_index(), to.to_index());
 
-                    match self.turn {
-                        White => {
-                            match promotion {
-                                Pieces::Queen => {
-                                    self.pieces.insert(to, Piece::new(to, White, Pieces::Queen))
-                                }
-                                Pieces::Rook => {
-                                    self.pieces.insert(to, Piece::new(to, White, Pieces::Rook))
-                                }
-                                Pieces::Bishop => self
-                                    .pieces
-                                    .insert(to, Piece::new(to, White, Pieces::Bishop)),
-                                Pieces::Knight => self
-                                    .pieces
-                                    .insert(to, Piece::new(to, White, Pieces::Knight)),
-                                _ => self.pieces.insert(to, Piece::new(to, White, Pieces::Queen)),
-                            };
-                        }
-                        Black => {
-                            match promotion {
-                                Pieces::Queen => {
-                                    self.pieces.insert(to, Piece::new(to, Black, Pieces::Queen))
-                                }
-                                Pieces::Rook => {
-                                    self.pieces.insert(to, Piece::new(to, Black, Pieces::Rook))
-                                }
-                                Pieces::Bishop => self
-                                    .pieces
-                                    .insert(to, Piece::new(to, Black, Pieces::Bishop)),
-                                Pieces::Knight => self
-                                    .pieces
-                                    .insert(to, Piece::new(to, Black, Pieces::Knight)),
-                                _ => self.pieces.insert(to, Piece::new(to, Black, Pieces::Queen)),
-                            };
-                        }
-                    }
+                let pawn = self.remove_piece(from).unwrap();
+                captured = self.remove_piece(to);
+                self.add_piece(to, Piece::new(to, pawn.get_color(), promotion));
+            }
+        }
 
-                    self.pieces.remove(&from);
-                } else {
-                    //warn!("Illegal promotion move from {} to {}", from, to);
-                    return false;
-                }
+        // captures and pawn moves can not be undone, so they restart the fifty move count
+        let is_pawn_move = match mov {
+            Move::Normal { to, .. } => {
+                self.squares[to.to_index()].unwrap().get_piece_type() == Pieces::Pawn
             }
+            Move::Promotion { .. } | Move::EnPassant { .. } => true,
+            Move::Castle { .. } => false,
+        };
+        if captured.is_some() || is_pawn_move {
+            self.modifiers.halfmove_clock = 0;
+        } else {
+            self.modifiers.halfmove_clock = self.modifiers.halfmove_clock.saturating_add(1);
         }
 
-        self.update_fen();
+        if self.turn == Black {
+            self.modifiers.fullmove_number = self.modifiers.fullmove_number.saturating_add(1);
+        }
 
         self.turn = match self.turn {
             White => Black,
             Black => White,
         };
+        self.hash ^= self.state_hash();
+        self.history.push(hash);
 
-        if let Some(_) = self.get_modifiers().en_passant {
-            //info!("En passant at {}", pos);
-        }
-
-        return true;
+        UndoInfo::new(mov, captured, modifiers, hash)
     }
 
-    pub fn undo_move(&mut self, mov: Move) {
-        match mov {
+    /// Takes back a move made with make_move or make_move_no_checks
+    /// Restores the pieces, the captured piece, the castling rights,
+    /// the en passant square and the turn exactly as they were before the move.
+    /// A resignation or claimed draw after the move is taken back as well.
+    /// Moves must be taken back in the reverse order they were made in
+    pub fn unmake_move(&mut self, undo: UndoInfo) {
+        self.turn = match self.turn {
+            White => Black,
+            Black => White,
+        };
+        self.modifiers = undo.get_modifiers();
+
+        match undo.get_move() {
             Move::Normal { from, to } => {
-                let mut piece = self.pieces.remove(&to).unwrap();
-                piece.set_position(from);
-                self.pieces.insert(from, piece);
+                let piece = self.remove_piece(to).unwrap();
+                self.add_piece(from, piece);
+            }
+            Move::Promotion { from, to, .. } => {
+                let piece = self.remove_piece(to).unwrap();
+                self.add_piece(from, Piece::new(from, piece.get_color(), Pieces::Pawn));
+            }
+            Move::EnPassant { from, to } => {
+                let pawn = self.remove_piece(to).unwrap();
+                self.add_piece(from, pawn);
+            }
+            Move::Castle { color, castle_type } => {
+                let (king_to, rook_from, rook_to) = match castle_type {
+                    KingSide => (6, 7, 5),
+                    QueenSide => (2, 0, 3),
+                };
+                let rank = match color {
+                    White => 0,
+                    Black => 7,
+                };
+
+                let king = self.remove_piece(Position::new(king_to, rank)).unwrap();
+                self.add_piece(Position::new(4, rank), king);
+
+                let rook = self.remove_piece(Position::new(rook_to, rank)).unwrap();
+                self.add_piece(Position::new(rook_from, rank), rook);
             }
-            _ => (),
         }
+
+        if let Some(captured) = undo.get_captured() {
+            self.add_piece(captured.get_position(), captured);
+        }
+
+        self.hash = undo.get_hash();
+        self.history.pop();
+        self.ending = None;
     }
 
+    /// Keeps the moves that do not leave the king of the moving side in check.
+    /// The moves can be of both colors, each is checked against the king of its own color
     fn verify_checks(&self, pseudo_moves: Vec<Move>) -> Vec<Move> {
         let mut moves = Vec::new();
+        let in_check = [self.is_in_check(White), self.is_in_check(Black)];
+        let pinned = [self.pinned_pieces(White), self.pinned_pieces(Black)];
 
         for mov in pseudo_moves {
-            match mov {
-                Move::Normal { from, to } => {
-                    let mut board = self.clone();
-                    board.pieces.remove(&to);
-                    let mut piece = board.pieces.remove(&from).unwrap();
-                    piece.set_position(to);
-                    board.pieces.insert(to, piece);
-
-                    if !board.is_in_check(self.get_turn()) {
-                        moves.push(mov);
-                    }
-                }
-                Move::Castle { color, castle_type } => {
-                    let mut board = self.clone();
-
-                    match castle_type {
-                        KingSide => {
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
-
-                            let king_to = match color {
-                                White => Position::new(5, 0),
-                                Black => Position::new(5, 7),
-                            };
-
-                            let mut king = board.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            board.pieces.insert(king_to, king);
-
-                            if board.is_in_check(self.get_turn()) {
-                                break;
-                            }
+            // a piece that is not pinned can not expose its king, so its moves only
+            // need a closer look when the king is in check or when the king itself moves.
+            // En passant removes two pawns from a rank at once, which can uncover the king
+            // in a way pins do not show, so it is always looked at.
+            // Castling is only generated when the king stays out of check all the way
+            let (from, to) = match mov {
+                Move::Normal { from, to }
+                | Move::Promotion { from, to, .. }
+                | Move::EnPassant { from, to } => (from, to),
+                Move::Castle { .. } => {
+                    moves.push(mov);
+                    continue;
+                }
+            };
+
+            let piece = self.squares[from.to_index()].unwrap();
+            let color = match piece.get_color() {
+                White => 0,
+                Black => 1,
+            };
+
+            if !in_check[color]
+                && !matches!(mov, Move::EnPassant { .. })
+                && piece.get_piece_type() != Pieces::King
+                && pinned[color] & (1 << from.to_index()) == 0
+            {
+                moves.push(mov);
+                continue;
+            }
 
-                            let mut board = self.clone();
-
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
-                            let king_to = match color {
-                                White => Position::new(6, 0),
-                                Black => Position::new(6, 7),
-                            };
-                            let rook_from = match color {
-                                White => Position::new(7, 0),
-                                Black => Position::new(7, 7),
-                            };
-                            let rook_to = match color {
-                                White => Position::new(5, 0),
-                                Black => Position::new(5, 7),
-                            };
-
-                            let mut king = board.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            board.pieces.insert(king_to, king);
-
-                            let mut rook = board.pieces.remove(&rook_from).unwrap();
-                            rook.set_position(rook_to);
-                            board.pieces.insert(rook_to, rook);
-                        }
-                        QueenSide => {
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
+            if self.is_king_safe_after(mov, from, to, piece.get_color()) {
+                moves.push(mov);
+            }
+        }
 
-                            let king_to = match color {
-                                White => Position::new(3, 0),
-                                Black => Position::new(3, 7),
-                            };
+        moves
+    }
 
-                            let mut board = self.clone();
+    /// Returns true if the king of the color is not attacked once the piece moved from one square
+    /// to the other, looking at the pieces that would be left on the board without moving them
+    fn is_king_safe_after(&self, mov: Move, from: Position, to: Position, color: Color) -> bool {
+        let kings = self.bitboards.pieces(color, Pieces::King);
+        if kings == 0 {
+            return true;
+        }
 
-                            let mut king = board.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            board.pieces.insert(king_to, king);
+        let king = if kings & (1 << from.to_index()) != 0 {
+            to.to_index()
+        } else {
+            kings.trailing_zeros() as usize
+        };
+        let mut occupancy = (self.bitboards.all() & !(1 << from.to_index())) | 1 << to.to_index();
+        // the pawn taken en passant does not stand on the square it is taken on
+        if let Move::EnPassant { .. } = mov {
+            occupancy &= !(1 << (from.get_y() as usize * 8 + to.get_x() as usize));
+        }
 
-                            if board.is_in_check(self.get_turn()) {
-                                break;
-                            }
+        // a piece that is taken does not attack anymore
+        self.attackers(king, occupancy, color.opponent()) & !(1 << to.to_index()) == 0
+    }
 
-                            let king_from = match color {
-                                White => Position::new(4, 0),
-                                Black => Position::new(4, 7),
-                            };
-                            let king_to = match color {
-                                White => Position::new(2, 0),
-                                Black => Position::new(2, 7),
-                            };
-                            let rook_from = match color {
-                                White => Position::new(0, 0),
-                                Black => Position::new(0, 7),
-                            };
-                            let rook_to = match color {
-                                White => Position::new(3, 0),
-                                Black => Position::new(3, 7),
-                            };
-
-                            let mut king = board.pieces.remove(&king_from).unwrap();
-                            king.set_position(king_to);
-                            board.pieces.insert(king_to, king);
-
-                            let mut rook = board.pieces.remove(&rook_from).unwrap();
-                            rook.set_position(rook_to);
-                            board.pieces.insert(rook_to, rook);
-                        }
-                    }
+    /// Returns the pieces of a color that attack the square with the given index,
+    /// with only the squares in the occupancy blocking the sliding pieces
+    fn attackers(&self, index: usize, occupancy: u64, by: Color) -> u64 {
+        let bitboards = &self.bitboards;
+        let queens = bitboards.pieces(by, Pieces::Queen);
+
+        let attackers = attacks::rook_attacks(index, occupancy)
+            & (bitboards.pieces(by, Pieces::Rook) | queens)
+            | attacks::bishop_attacks(index, occupancy)
+                & (bitboards.pieces(by, Pieces::Bishop) | queens)
+            | attacks::knight_attacks(index) & bitboards.pieces(by, Pieces::Knight)
+            | attacks::king_attacks(index) & bitboards.pieces(by, Pieces::King)
+            // a pawn attacks the square if a pawn on the square would attack the pawn
+            | attacks::pawn_attacks(index, by.opponent()) & bitboards.pieces(by, Pieces::Pawn);
+
+        attackers & occupancy
+    }
 
-                    if !board.is_in_check(self.get_turn()) {
-                        moves.push(mov);
-                    }
-                }
-                Move::Promotion {
-                    from,
-                    to,
-                    promotion: _,
-                } => {
-                    let mut board = self.clone();
-
-                    board.pieces.remove(&to);
-                    let mut piece = board.pieces.remove(&from).unwrap();
-                    piece.set_position(to);
-                    board.pieces.insert(to, piece);
-
-                    if !board.is_in_check(self.get_turn()) {
-                        moves.push(mov);
-                    }
-                }
+    /// Returns the bitboard of the pieces of the color that are pinned to their king
+    fn pinned_pieces(&self, color: Color) -> u64 {
+        let kings = self.bitboards.pieces(color, Pieces::King);
+        if kings == 0 {
+            return 0;
+        }
+
+        let king = kings.trailing_zeros() as usize;
+        let opponent = match color {
+            White => Black,
+            Black => White,
+        };
+        let occupancy = self.bitboards.all();
+        let opponent_pieces = self.bitboards.color(opponent);
+        let queens = self.bitboards.pieces(opponent, Pieces::Queen);
+
+        // sliders that would attack the king if none of the own pieces were in the way
+        let rooks = attacks::rook_attacks(king, opponent_pieces)
+            & (self.bitboards.pieces(opponent, Pieces::Rook) | queens);
+        let bishops = attacks::bishop_attacks(king, opponent_pieces)
+            & (self.bitboards.pieces(opponent, Pieces::Bishop) | queens);
+
+        let mut pinned = 0;
+        for pinner in Squares(rooks) {
+            let between = attacks::rook_attacks(king, 1 << pinner)
+                & attacks::rook_attacks(pinner, 1 << king)
+                & occupancy;
+            if between.count_ones() == 1 {
+                pinned |= between;
+            }
+        }
+        for pinner in Squares(bishops) {
+            let between = attacks::bishop_attacks(king, 1 << pinner)
+                & attacks::bishop_attacks(pinner, 1 << king)
+                & occupancy;
+            if between.count_ones() == 1 {
+                pinned |= between;
             }
         }
 
-        moves
+        pinned & self.bitboards.color(color)
     }
 
     fn get_all_pseudo_legal_moves_for_white(&self) -> Vec<Move> {
-        let mut moves = Vec::new();
-        for piece in self.get_all_pieces().values() {
-            if piece.get_color() == White {
-                moves.append(&mut piece.get_all_legal_moves(self));
-            }
-        }
-        moves
+        self.get_pseudo_legal_moves_in(self.bitboards.color(White))
     }
 
     fn get_all_pseudo_legal_moves_for_black(&self) -> Vec<Move> {
-        let mut moves = Vec::new();
-        for piece in self.get_all_pieces().values() {
-            if piece.get_color() == Black {
-                moves.append(&mut piece.get_all_legal_moves(self));
-            }
-        }
-        moves
+        self.get_pseudo_legal_moves_in(self.bitboards.color(Black))
     }
 
     fn get_all_pseudo_legal_moves(&self) -> Vec<Move> {
+        self.get_pseudo_legal_moves_in(self.bitboards.all())
+    }
+
+    /// Returns the pseudo legal moves of the pieces on the squares of the bitboard
+    fn get_pseudo_legal_moves_in(&self, bitboard: u64) -> Vec<Move> {
         let mut moves = Vec::new();
-        for piece in self.get_all_pieces().values() {
-            moves.append(&mut piece.get_all_legal_moves(self));
+        for index in Squares(bitboard) {
+            if let Some(piece) = &self.squares[index] {
+                moves.append(&mut piece.get_all_legal_moves(self));
+            }
         }
         moves
     }
-
-    fn update_fen(&mut self) {
-        self.fen = self.generate_fen();
-    }
 }
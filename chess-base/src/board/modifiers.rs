@@ -1,31 +1,21 @@
-// TODO: will also be important for implementing the 50 move rule
-// TODO: the 3 fold repetition rule and insufficient material rule
-
+use crate::core::castles::CastlingRights;
 use crate::core::position::Position;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Modifiers {
-    pub can_white_castle_kingside: bool,
-    pub can_white_castle_queenside: bool,
-    pub can_black_castle_kingside: bool,
-    pub can_black_castle_queenside: bool,
+    pub castling_rights: CastlingRights,
     pub en_passant: Option<Position>,
-    pub promotion: Option<Position>,
-    pub halfmove_clock: u8,
-    // pub repetition_count: u8,
-    // pub insufficient_material: bool,
+    pub halfmove_clock: u16,
+    pub fullmove_number: u16,
 }
 
 impl Modifiers {
     pub fn new() -> Modifiers {
         Modifiers {
-            can_white_castle_kingside: true,
-            can_white_castle_queenside: true,
-            can_black_castle_kingside: true,
-            can_black_castle_queenside: true,
+            castling_rights: CastlingRights::all(),
             en_passant: None,
-            promotion: None,
             halfmove_clock: 0,
+            fullmove_number: 1,
         }
     }
 }
@@ -0,0 +1,79 @@
+/// Outcome module
+/// Contains the types returned by Board::make_move:
+/// what a successful move did to the game, or why a move was rejected
+use super::undo::UndoInfo;
+use crate::core::castles::Castles;
+use crate::core::color::Color;
+use crate::core::pieces::Pieces;
+use crate::core::position::Position;
+use std::fmt;
+
+/// The effects of a move that was made on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MoveOutcome {
+    /// The type of the piece that was captured, if any
+    pub captured: Option<Pieces>,
+    /// The opponent is in check after the move
+    pub is_check: bool,
+    /// The opponent is checkmated after the move
+    pub is_checkmate: bool,
+    /// The move captured a pawn en passant
+    pub is_en_passant: bool,
+    /// The record needed to take the move back with Board::unmake_move
+    pub undo: UndoInfo,
+}
+
+/// The reason a move was rejected by Board::make_move
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The square is not on the board
+    OffBoard(Position),
+    /// There is no piece on the square the move starts from
+    NoPiece(Position),
+    /// The piece on the square belongs to the side that is not on move
+    OpponentPiece(Position),
+    /// The castling move is for the side that is not on move
+    NotYourTurn(Color),
+    /// The piece can not move from the first square to the second one
+    InvalidMove(Position, Position),
+    /// A pawn reaches the last rank without saying what it promotes to
+    MissingPromotion,
+    /// The move is a promotion but it is not a pawn reaching the last rank
+    NotAPromotion,
+    /// Pawns can only promote to a knight, bishop, rook or queen
+    InvalidPromotionPiece(Pieces),
+    /// Castling is not allowed on that side
+    CastlingNotAllowed(Castles),
+    /// The move would leave the own king in check
+    LeavesKingInCheck,
+    /// The game has already ended
+    GameOver,
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::OffBoard(position) => write!(f, "square {:#} is off the board", position),
+            MoveError::NoPiece(position) => write!(f, "there is no piece on {}", position),
+            MoveError::OpponentPiece(position) => {
+                write!(f, "the piece on {} belongs to the opponent", position)
+            }
+            MoveError::NotYourTurn(color) => write!(f, "it is not {:?}'s turn", color),
+            MoveError::InvalidMove(from, to) => {
+                write!(f, "the piece on {} can not move to {}", from, to)
+            }
+            MoveError::MissingPromotion => write!(f, "the pawn must promote to another piece"),
+            MoveError::NotAPromotion => write!(f, "only a pawn reaching the last rank promotes"),
+            MoveError::InvalidPromotionPiece(piece) => {
+                write!(f, "a pawn can not promote to {:?}", piece)
+            }
+            MoveError::CastlingNotAllowed(castle_type) => {
+                write!(f, "castling {:?} is not allowed", castle_type)
+            }
+            MoveError::LeavesKingInCheck => write!(f, "the move leaves the king in check"),
+            MoveError::GameOver => write!(f, "the game is over"),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
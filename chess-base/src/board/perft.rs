@@ -0,0 +1,54 @@
+/// Perft module
+/// Counts the leaf nodes of the move tree up to a depth,
+/// which can be compared to published counts to find bugs in the move generation
+use super::Board;
+use crate::core::mov::Move;
+
+impl Board {
+    /// Returns the number of move sequences of the given length from this position
+    pub fn perft(&self, depth: u32) -> u64 {
+        self.clone().count_moves(depth)
+    }
+
+    /// Returns every legal move with the number of move sequences of the given length
+    /// that start with it, in the order the moves are generated.
+    /// The counts add up to perft(depth), comparing them with another engine
+    /// narrows a wrong total down to the moves that cause it
+    pub fn perft_divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        let mut board = self.clone();
+
+        board
+            .get_all_legal_moves_for(board.get_turn())
+            .into_iter()
+            .map(|mov| {
+                let undo = board.make_move_no_checks(mov);
+                let nodes = board.count_moves(depth.saturating_sub(1));
+                board.unmake_move(undo);
+                (mov, nodes)
+            })
+            .collect()
+    }
+
+    /// Counts the leaf nodes of the move tree, making and unmaking moves on this board
+    fn count_moves(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.get_all_legal_moves_for(self.get_turn());
+
+        // the moves at the last level do not have to be played to be counted
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mov in moves {
+            let undo = self.make_move_no_checks(mov);
+            nodes += self.count_moves(depth - 1);
+            self.unmake_move(undo);
+        }
+
+        nodes
+    }
+}
@@ -0,0 +1,119 @@
+/// See module
+/// Contains the static exchange evaluation, which works out what a capture wins or loses
+/// when both sides keep recapturing on the square with their least valuable piece
+use super::Board;
+use crate::core::bitboard::Squares;
+use crate::core::mov::Move;
+use crate::core::pieces::Pieces;
+
+/// The pieces in the order they are sent into an exchange, cheapest first
+const EXCHANGE_ORDER: [Pieces; 6] = [
+    Pieces::Pawn,
+    Pieces::Knight,
+    Pieces::Bishop,
+    Pieces::Rook,
+    Pieces::Queen,
+    Pieces::King,
+];
+
+/// Returns the value of a piece in centipawns as the exchange evaluation counts it
+fn exchange_value(piece_type: Pieces) -> i32 {
+    match piece_type {
+        Pieces::Pawn => 100,
+        Pieces::Knight => 300,
+        Pieces::Bishop => 300,
+        Pieces::Rook => 500,
+        Pieces::Queen => 900,
+        Pieces::King => 20_000,
+    }
+}
+
+impl Board {
+    /// Returns true if the move takes a piece
+    pub fn is_capture(&self, mov: Move) -> bool {
+        match mov {
+            Move::Normal { to, .. } | Move::Promotion { to, .. } => self.get_piece(to).is_some(),
+            Move::EnPassant { .. } => true,
+            Move::Castle { .. } => false,
+        }
+    }
+
+    /// Returns the material the side to move wins with the move in centipawns,
+    /// negative if it loses material, assuming both sides keep taking on the square
+    /// with their least valuable piece for as long as that pays off.
+    /// A move that takes nothing is scored by whether the piece can be taken where it lands.
+    /// Pins are not looked at, and the move itself is not checked to be legal,
+    /// a move from or to a square off the board scores 0
+    pub fn see(&self, mov: Move) -> i32 {
+        let (from, to) = match mov {
+            Move::Normal { from, to }
+            | Move::Promotion { from, to, .. }
+            | Move::EnPassant { from, to } => (from, to),
+            Move::Castle { .. } => return 0,
+        };
+        if [from, to]
+            .iter()
+            .any(|position| position.get_x() > 7 || position.get_y() > 7)
+        {
+            return 0;
+        }
+        let Some(piece) = self.get_piece(from) else {
+            return 0;
+        };
+
+        let target = to.to_index();
+        let mut occupancy = self.bitboards.all() & !(1 << from.to_index());
+        let mut gains = vec![match mov {
+            Move::EnPassant { .. } => exchange_value(Pieces::Pawn),
+            _ => self
+                .get_piece(to)
+                .map_or(0, |captured| exchange_value(captured.get_piece_type())),
+        }];
+        // the value of the piece that stands on the square and can be taken next
+        let mut on_square = exchange_value(piece.get_piece_type());
+
+        match mov {
+            Move::Promotion { promotion, .. } => {
+                gains[0] += exchange_value(promotion) - exchange_value(Pieces::Pawn);
+                on_square = exchange_value(promotion);
+            }
+            // the pawn taken en passant does not stand on the square it is taken on
+            Move::EnPassant { .. } => {
+                occupancy &= !(1 << (from.get_y() as usize * 8 + to.get_x() as usize));
+            }
+            _ => (),
+        }
+
+        let mut side = piece.get_color().opponent();
+        loop {
+            let attackers = self.attackers(target, occupancy, side);
+            let Some((square, piece_type)) = EXCHANGE_ORDER.iter().find_map(|&piece_type| {
+                Squares(attackers & self.bitboards.pieces(side, piece_type))
+                    .next()
+                    .map(|square| (square, piece_type))
+            }) else {
+                break;
+            };
+
+            // the king can only take if the piece it takes is not defended
+            if piece_type == Pieces::King && self.attackers(target, occupancy, side.opponent()) != 0
+            {
+                break;
+            }
+
+            gains.push(on_square - gains[gains.len() - 1]);
+            on_square = exchange_value(piece_type);
+            occupancy &= !(1 << square);
+            side = side.opponent();
+        }
+
+        // every side stops taking as soon as going on would lose material
+        while gains.len() > 1 {
+            let gain = gains.pop().unwrap_or(0);
+            let last = gains.len() - 1;
+            gains[last] = -(-gains[last]).max(gain);
+        }
+
+        gains[0]
+    }
+}
@@ -0,0 +1,66 @@
+/// Status module
+/// Contains the types returned by Board::status:
+/// whether the game is still going on, and how it ended if it is not
+use crate::core::color::Color;
+use std::fmt;
+
+/// The state of the game in the current position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    /// The side to move has legal moves and the game has not ended otherwise
+    Ongoing,
+    /// The side to move is in check and has no legal moves
+    Checkmate { winner: Color },
+    /// The side to move is not in check but has no legal moves
+    Stalemate,
+    /// The game ended in a draw, either automatically or because it was claimed
+    Draw(DrawReason),
+    /// One of the players resigned
+    Resigned { winner: Color },
+}
+
+impl GameStatus {
+    /// Returns true if the game has ended
+    pub fn is_over(&self) -> bool {
+        *self != GameStatus::Ongoing
+    }
+}
+
+/// The reason a game ended in a draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawReason {
+    /// The same position occurred three times and the side to move claimed the draw
+    ThreefoldRepetition,
+    /// The same position occurred five times
+    FivefoldRepetition,
+    /// Fifty moves by each side without a capture or pawn move, and the side to move claimed the draw
+    FiftyMoveRule,
+    /// Seventy-five moves by each side without a capture or pawn move
+    SeventyFiveMoveRule,
+    /// Neither side has enough material left to checkmate
+    InsufficientMaterial,
+}
+
+impl fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameStatus::Ongoing => write!(f, "the game is ongoing"),
+            GameStatus::Checkmate { winner } => write!(f, "{:?} wins by checkmate", winner),
+            GameStatus::Stalemate => write!(f, "draw by stalemate"),
+            GameStatus::Draw(reason) => write!(f, "draw by {}", reason),
+            GameStatus::Resigned { winner } => write!(f, "{:?} wins by resignation", winner),
+        }
+    }
+}
+
+impl fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DrawReason::ThreefoldRepetition => write!(f, "threefold repetition"),
+            DrawReason::FivefoldRepetition => write!(f, "fivefold repetition"),
+            DrawReason::FiftyMoveRule => write!(f, "the fifty move rule"),
+            DrawReason::SeventyFiveMoveRule => write!(f, "the seventy-five move rule"),
+            DrawReason::InsufficientMaterial => write!(f, "insufficient material"),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+/// Undo module
+/// Contains the record that is returned when a move is made,
+/// which holds everything Board::unmake_move needs to take the move back
+use super::modifiers::Modifiers;
+use crate::core::mov::Move;
+use crate::core::piece::Piece;
+
+/// The state of the board that a move destroys
+/// The captured piece keeps the position it was captured on,
+/// which differs from the target square of the move for en passant captures
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UndoInfo {
+    mov: Move,
+    captured: Option<Piece>,
+    modifiers: Modifiers,
+    hash: u64,
+}
+
+impl UndoInfo {
+    pub(super) fn new(
+        mov: Move,
+        captured: Option<Piece>,
+        modifiers: Modifiers,
+        hash: u64,
+    ) -> UndoInfo {
+        UndoInfo {
+            mov,
+            captured,
+            modifiers,
+            hash,
+        }
+    }
+
+    /// Returns the move this record takes back
+    pub fn get_move(&self) -> Move {
+        self.mov
+    }
+
+    /// Returns the piece the move captured, if any
+    pub fn get_captured(&self) -> Option<Piece> {
+        self.captured
+    }
+
+    /// Returns the castling rights, en passant square and clocks from before the move
+    pub(super) fn get_modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Returns the hash of the position from before the move
+    pub(super) fn get_hash(&self) -> u64 {
+        self.hash
+    }
+}
@@ -0,0 +1,350 @@
+// This module contains the attack tables used for move generation and check detection
+
+use super::color::Color;
+use std::sync::OnceLock;
+
+/// Squares a knight on the square with the given index attacks
+pub fn knight_attacks(index: usize) -> u64 {
+    KNIGHT_ATTACKS[index]
+}
+
+/// Squares a king on the square with the given index attacks
+pub fn king_attacks(index: usize) -> u64 {
+    KING_ATTACKS[index]
+}
+
+/// Squares a pawn of the given color on the square with the given index attacks
+pub fn pawn_attacks(index: usize, color: Color) -> u64 {
+    match color {
+        Color::White => PAWN_ATTACKS[0][index],
+        Color::Black => PAWN_ATTACKS[1][index],
+    }
+}
+
+/// Squares a rook on the square with the given index attacks,
+/// stopping at (and including) the first occupied square in every direction
+pub fn rook_attacks(index: usize, occupancy: u64) -> u64 {
+    let tables = tables();
+    tables.attacks[tables.rook[index].index(occupancy)]
+}
+
+/// Squares a bishop on the square with the given index attacks,
+/// stopping at (and including) the first occupied square in every direction
+pub fn bishop_attacks(index: usize, occupancy: u64) -> u64 {
+    let tables = tables();
+    tables.attacks[tables.bishop[index].index(occupancy)]
+}
+
+/// Squares a queen on the square with the given index attacks
+pub fn queen_attacks(index: usize, occupancy: u64) -> u64 {
+    rook_attacks(index, occupancy) | bishop_attacks(index, occupancy)
+}
+
+const KNIGHT_JUMPS: [(i32, i32); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+const KING_STEPS: [(i32, i32); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+const KNIGHT_ATTACKS: [u64; 64] = leaper_attacks(&KNIGHT_JUMPS);
+const KING_ATTACKS: [u64; 64] = leaper_attacks(&KING_STEPS);
+
+// white pawns capture towards the eighth rank, black pawns towards the first
+const PAWN_ATTACKS: [[u64; 64]; 2] = [
+    leaper_attacks(&[(-1, 1), (1, 1)]),
+    leaper_attacks(&[(-1, -1), (1, -1)]),
+];
+
+/// Builds the attack table of a piece that jumps by fixed offsets
+const fn leaper_attacks<const N: usize>(offsets: &[(i32, i32); N]) -> [u64; 64] {
+    let mut table = [0; 64];
+    let mut index = 0;
+    while index < 64 {
+        let x = (index % 8) as i32;
+        let y = (index / 8) as i32;
+        let mut i = 0;
+        while i < N {
+            let (dx, dy) = offsets[i];
+            if x + dx >= 0 && x + dx < 8 && y + dy >= 0 && y + dy < 8 {
+                table[index] |= 1 << ((y + dy) * 8 + x + dx);
+            }
+            i += 1;
+        }
+        index += 1;
+    }
+    table
+}
+
+/// Walks from the square in every direction until the edge of the board or an occupied square
+fn sliding_attacks(index: usize, occupancy: u64, directions: &[(i32, i32); 4]) -> u64 {
+    let mut attacks = 0;
+    for (dx, dy) in directions {
+        let mut x = (index % 8) as i32 + dx;
+        let mut y = (index / 8) as i32 + dy;
+        while (0..8).contains(&x) && (0..8).contains(&y) {
+            let bit = 1 << (y * 8 + x);
+            attacks |= bit;
+            if occupancy & bit != 0 {
+                break;
+            }
+            x += dx;
+            y += dy;
+        }
+    }
+    attacks
+}
+
+/// The squares whose occupancy matters for a slider on the square,
+/// which are the squares it attacks on an empty board without the edge of the board
+fn relevant_occupancy(index: usize, directions: &[(i32, i32); 4]) -> u64 {
+    let mut mask = 0;
+    for (dx, dy) in directions {
+        let mut x = (index % 8) as i32 + dx;
+        let mut y = (index / 8) as i32 + dy;
+        while (0..8).contains(&(x + dx)) && (0..8).contains(&(y + dy)) {
+            mask |= 1 << (y * 8 + x);
+            x += dx;
+            y += dy;
+        }
+    }
+    mask
+}
+
+/// Magic bitboard entry of one square
+/// Multiplying the relevant occupancy by the magic number gathers its bits
+/// into the top bits, which then index the attack table of the square
+#[derive(Debug, Clone, Copy)]
+struct Magic {
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl Magic {
+    fn index(&self, occupancy: u64) -> usize {
+        self.offset + ((occupancy & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+/// The magic entries of the sliders and the attack table they index into
+struct SliderTables {
+    rook: [Magic; 64],
+    bishop: [Magic; 64],
+    attacks: Vec<u64>,
+}
+
+static TABLES: OnceLock<SliderTables> = OnceLock::new();
+
+fn tables() -> &'static SliderTables {
+    TABLES.get_or_init(SliderTables::new)
+}
+
+impl SliderTables {
+    fn new() -> SliderTables {
+        let mut attacks = Vec::new();
+        let rook = Self::fill(&mut attacks, &ROOK_MAGICS, &ROOK_DIRECTIONS);
+        let bishop = Self::fill(&mut attacks, &BISHOP_MAGICS, &BISHOP_DIRECTIONS);
+
+        SliderTables {
+            rook,
+            bishop,
+            attacks,
+        }
+    }
+
+    /// Appends the attacks of every occupancy of every square to the table
+    fn fill(
+        attacks: &mut Vec<u64>,
+        magics: &[u64; 64],
+        directions: &[(i32, i32); 4],
+    ) -> [Magic; 64] {
+        let mut entries = [Magic {
+            mask: 0,
+            magic: 0,
+            shift: 0,
+            offset: 0,
+        }; 64];
+
+        for (index, entry) in entries.iter_mut().enumerate() {
+            let mask = relevant_occupancy(index, directions);
+            let bits = mask.count_ones();
+
+            *entry = Magic {
+                mask,
+                magic: magics[index],
+                shift: 64 - bits,
+                offset: attacks.len(),
+            };
+            attacks.resize(attacks.len() + (1 << bits), 0);
+
+            // enumerate all subsets of the mask with the carry rippler trick
+            let mut occupancy: u64 = 0;
+            loop {
+                attacks[entry.index(occupancy)] = sliding_attacks(index, occupancy, directions);
+                occupancy = occupancy.wrapping_sub(mask) & mask;
+                if occupancy == 0 {
+                    break;
+                }
+            }
+        }
+
+        entries
+    }
+}
+
+// Magic numbers found by a random search, every one maps all the occupancies
+// of its square to a table index without destructive collisions
+
+const ROOK_MAGICS: [u64; 64] = [
+    0x008000908064c000,
+    0x0040200040001000,
+    0x0180100080a0010a,
+    0x8880041000800800,
+    0x1200100201200804,
+    0x0200020004011008,
+    0x2180010000800600,
+    0x0200005088210204,
+    0x0400800040008021,
+    0x0400400020005000,
+    0x8240801000200080,
+    0x8611001004200900,
+    0x008180800c001800,
+    0x0100800200800400,
+    0x0a02000102000408,
+    0x8020802300104280,
+    0x0080004000402000,
+    0xe010104000402000,
+    0x0800808010002000,
+    0xa280210008100100,
+    0x0001818014000800,
+    0xa002010100080400,
+    0x0080240001020870,
+    0x0001020004048845,
+    0x0081826280004004,
+    0x2020810900284000,
+    0x0200100080802000,
+    0x0200080080100080,
+    0x8083080100100500,
+    0x4406000901000400,
+    0x0005020080800100,
+    0x0090204200008114,
+    0x0010400094800420,
+    0x0900804000802002,
+    0x0201001841002000,
+    0x4100080080801000,
+    0x4540040080800800,
+    0x0002001004040020,
+    0x0281195814001002,
+    0x1240800040800100,
+    0x0880042000524004,
+    0x02c080410206002c,
+    0x0801200241050010,
+    0x8400080010008080,
+    0x0008000500090010,
+    0x0082009084020008,
+    0x4012000108020004,
+    0x9000104d08860004,
+    0x2004204114800100,
+    0x0148802112400300,
+    0x0202842000100880,
+    0x001b080080900080,
+    0x001a002008100600,
+    0x0004008004020080,
+    0x5181000600040300,
+    0x0000044401128a00,
+    0x8044110480002441,
+    0x2008110084402202,
+    0x90806005090010c1,
+    0x000420310a004a42,
+    0x0023001004020801,
+    0x0882001008040102,
+    0x000230088118020c,
+    0x0000019025040042,
+];
+
+const BISHOP_MAGICS: [u64; 64] = [
+    0x0045010808008680,
+    0x2002080204004898,
+    0x0210009a10400006,
+    0x0824050200810200,
+    0x0006061105004090,
+    0x00010108c0000000,
+    0x0814040282104004,
+    0x0012012201106800,
+    0x10823014100c1040,
+    0x0080c2088802808c,
+    0x0281108410404000,
+    0x0101212041826200,
+    0x0020141028221058,
+    0x2201020202200202,
+    0x000082a801482000,
+    0x0000008401411044,
+    0x0007103014300404,
+    0x0002091110010100,
+    0x42140012040c0808,
+    0x0800808802004020,
+    0x90c4004210140000,
+    0x0800200900a01000,
+    0x00d0400201108810,
+    0x80820183814412a0,
+    0x00a01008202202b4,
+    0x01c2021a09500402,
+    0x0084440208042400,
+    0x800400400c090100,
+    0xba10040010802100,
+    0xd182009006005000,
+    0x5011021001009004,
+    0x0020420200510400,
+    0x0292104000468800,
+    0x00043009091c0500,
+    0x0280441000020025,
+    0x0042820080080080,
+    0x0440101010010040,
+    0x1000900100808080,
+    0x0108108120089800,
+    0x0044010200012682,
+    0xc002500420900400,
+    0x0040482210710800,
+    0x0002060024000200,
+    0x0281020a44000800,
+    0xa0021200a4000200,
+    0x0001301000840840,
+    0x2868500108444220,
+    0x0004111041000200,
+    0x8044020842080200,
+    0x0000220104210200,
+    0x0000021201044000,
+    0x0000280884040028,
+    0x4012114010858003,
+    0x0000081004082b88,
+    0x3892700508208002,
+    0x00220a041b060400,
+    0x0812020284014881,
+    0x010434a282103100,
+    0x0490400824020800,
+    0x4a20002c00208800,
+    0x000000a011020200,
+    0x4002940a02482202,
+    0x5100100202140406,
+    0x02102000840540c1,
+];
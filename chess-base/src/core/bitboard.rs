@@ -0,0 +1,97 @@
+// This module contains the bitboard representation of the pieces
+
+use super::color::Color;
+use super::pieces::Pieces;
+
+/// A core struct that keeps the pieces on the board as bitboards.
+/// There is one u64 for every combination of piece type and color,
+/// bit n of a bitboard is set when the square with index n holds such a piece.
+/// The squares are indexed from a1 (0) to h8 (63), see Position::to_index.
+///
+/// Next to the piece bitboards there is an occupancy mask for every color,
+/// which is kept in sync when pieces are added or removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bitboards {
+    pieces: [[u64; 6]; 2],
+    occupancy: [u64; 2],
+}
+
+impl Bitboards {
+    /// Creates a new set of empty bitboards
+    pub fn new() -> Bitboards {
+        Bitboards {
+            pieces: [[0; 6]; 2],
+            occupancy: [0; 2],
+        }
+    }
+
+    /// Sets the bit of a piece on the square with the given index
+    pub fn add(&mut self, index: usize, color: Color, piece_type: Pieces) {
+        let bit = 1 << index;
+        self.pieces[color_index(color)][piece_index(piece_type)] |= bit;
+        self.occupancy[color_index(color)] |= bit;
+    }
+
+    /// Clears the bit of a piece on the square with the given index
+    pub fn remove(&mut self, index: usize, color: Color, piece_type: Pieces) {
+        let bit = !(1 << index);
+        self.pieces[color_index(color)][piece_index(piece_type)] &= bit;
+        self.occupancy[color_index(color)] &= bit;
+    }
+
+    /// Returns the bitboard of all the pieces of one type and color
+    pub fn pieces(&self, color: Color, piece_type: Pieces) -> u64 {
+        self.pieces[color_index(color)][piece_index(piece_type)]
+    }
+
+    /// Returns the occupancy mask of one color
+    pub fn color(&self, color: Color) -> u64 {
+        self.occupancy[color_index(color)]
+    }
+
+    /// Returns the occupancy mask of both colors
+    pub fn all(&self) -> u64 {
+        self.occupancy[0] | self.occupancy[1]
+    }
+}
+
+impl Default for Bitboards {
+    fn default() -> Self {
+        Bitboards::new()
+    }
+}
+
+/// Iterator over the indices of the set bits of a bitboard, from the lowest to the highest
+pub struct Squares(pub u64);
+
+impl Iterator for Squares {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(index)
+    }
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+fn piece_index(piece_type: Pieces) -> usize {
+    match piece_type {
+        Pieces::Pawn => 0,
+        Pieces::Knight => 1,
+        Pieces::Bishop => 2,
+        Pieces::Rook => 3,
+        Pieces::Queen => 4,
+        Pieces::King => 5,
+    }
+}
@@ -1,5 +1,84 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+use super::color::Color;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Castles {
     KingSide,
     QueenSide,
-}
\ No newline at end of file
+}
+
+/// The castling rights of both colors, kept as a set of bits:
+/// white kingside, white queenside, black kingside and black queenside.
+/// A right can only be taken away by the board, when the king or the rook it belongs to
+/// moves or the rook is captured, so other code can look at the rights but not change them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CastlingRights(u8);
+
+impl CastlingRights {
+    /// Returns the rights with every castling allowed, as in the starting position
+    pub fn all() -> CastlingRights {
+        CastlingRights(0b1111)
+    }
+
+    /// Returns the rights with no castling allowed
+    pub fn none() -> CastlingRights {
+        CastlingRights(0)
+    }
+
+    /// Returns true if the color may still castle to the side
+    pub fn has(&self, color: Color, castle_type: Castles) -> bool {
+        self.0 & bit(color, castle_type) != 0
+    }
+
+    /// Returns true if no castling is allowed anymore
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the rights as bits, white kingside being the lowest
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub(crate) fn insert(&mut self, color: Color, castle_type: Castles) {
+        self.0 |= bit(color, castle_type);
+    }
+
+    /// Takes away both rights of the color
+    pub(crate) fn remove_color(&mut self, color: Color) {
+        self.0 &= !(bit(color, Castles::KingSide) | bit(color, Castles::QueenSide));
+    }
+
+    /// Takes away the rights that a move between the squares with the given indices ends:
+    /// moving the king or a rook from its starting square, or capturing a rook on it
+    pub(crate) fn update(&mut self, from: usize, to: usize) {
+        self.0 &= !(lost_on(from) | lost_on(to));
+    }
+}
+
+impl Default for CastlingRights {
+    fn default() -> Self {
+        CastlingRights::all()
+    }
+}
+
+fn bit(color: Color, castle_type: Castles) -> u8 {
+    match (color, castle_type) {
+        (Color::White, Castles::KingSide) => 0b0001,
+        (Color::White, Castles::QueenSide) => 0b0010,
+        (Color::Black, Castles::KingSide) => 0b0100,
+        (Color::Black, Castles::QueenSide) => 0b1000,
+    }
+}
+
+/// Returns the rights that are lost when a piece moves from or to the square
+fn lost_on(index: usize) -> u8 {
+    match index {
+        0 => 0b0010,
+        4 => 0b0011,
+        7 => 0b0001,
+        56 => 0b1000,
+        60 => 0b1100,
+        63 => 0b0100,
+        _ => 0,
+    }
+}
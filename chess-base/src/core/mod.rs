@@ -4,4 +4,8 @@ pub mod position;
 pub mod direction;
 pub mod pieces;
 pub mod castles;
-pub mod piece;
\ No newline at end of file
+pub mod piece;
+pub mod bitboard;
+pub mod attacks;
+pub mod san;
+pub mod uci;
\ No newline at end of file
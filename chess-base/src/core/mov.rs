@@ -5,11 +5,12 @@ use super::pieces::Pieces;
 use super::position::Position;
 
 /// Represents a move on the board
-/// It has 3 variants:
+/// It has 4 variants:
 /// Normal: a normal move
 /// Promotion: a move that promotes a pawn
 /// Castle: a move that involves castleing a king
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// EnPassant: a pawn capturing a pawn that just moved two squares past it
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Move {
     Normal {
         from: Position,
@@ -24,4 +25,10 @@ pub enum Move {
         color: Color,
         castle_type: Castles,
     },
+    /// The captured pawn stands next to the square the capturing pawn moves from,
+    /// not on the square it moves to
+    EnPassant {
+        from: Position,
+        to: Position,
+    },
 }
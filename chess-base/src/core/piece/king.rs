@@ -1,120 +1,69 @@
 use super::*;
 use crate::board::Board;
+use crate::core::attacks;
 use crate::core::castles::Castles;
 use crate::core::color::Color;
-use crate::core::direction::Direction;
 use crate::core::mov::Move;
 
 impl Piece {
     pub fn get_all_legal_moves_king(&self, board: &Board) -> Vec<Move> {
-        let start = self.position;
-        let directions = vec![
-            Direction::Up,
-            Direction::UpRight,
-            Direction::Right,
-            Direction::DownRight,
-            Direction::Down,
-            Direction::DownLeft,
-            Direction::Left,
-            Direction::UpLeft,
-        ];
-        let mut moves = Vec::new();
+        let targets = attacks::king_attacks(self.position.to_index())
+            & !board.get_bitboards().color(self.get_color());
+        let mut moves = self.moves_to(targets);
 
-        for direction in directions {
-            if let Some(target) = start.increment(direction, 1) {
-                if let Some(piece) = board.get_piece(target) {
-                    if piece.get_color() != self.get_color() {
-                        let mov = Move::Normal {
-                            from: start,
-                            to: target,
-                        };
-                        moves.push(mov);
-                    }
-                } else {
-                    let mov = Move::Normal {
-                        from: start,
-                        to: target,
-                    };
-                    moves.push(mov);
-                }
+        for castle_type in [Castles::KingSide, Castles::QueenSide] {
+            if self.can_castle(board, castle_type) {
+                moves.push(Move::Castle {
+                    color: self.get_color(),
+                    castle_type,
+                });
             }
         }
 
-        match self.get_color() {
-            Color::White => {
-                if board.get_modifiers().can_white_castle_kingside {
-                    if let None =
-                        board.get_piece(self.get_position().increment(Direction::Right, 1).unwrap())
-                    {
-                        if let None = board
-                            .get_piece(self.get_position().increment(Direction::Right, 2).unwrap())
-                        {
-                            let mov = Move::Castle {
-                                color: Color::White,
-                                castle_type: Castles::KingSide,
-                            };
-                            moves.push(mov);
-                        }
-                    };
-                }
-                if board.get_modifiers().can_white_castle_queenside {
-                    if let None =
-                        board.get_piece(self.get_position().increment(Direction::Left, 1).unwrap())
-                    {
-                        if let None = board
-                            .get_piece(self.get_position().increment(Direction::Left, 2).unwrap())
-                        {
-                            if let None = board.get_piece(
-                                self.get_position().increment(Direction::Left, 3).unwrap(),
-                            ) {
-                                let mov = Move::Castle {
-                                    color: Color::White,
-                                    castle_type: Castles::QueenSide,
-                                };
-                                moves.push(mov);
-                            }
-                        }
-                    };
-                }
-            }
-            Color::Black => {
-                if board.get_modifiers().can_black_castle_kingside {
-                    if let None =
-                        board.get_piece(self.get_position().increment(Direction::Right, 1).unwrap())
-                    {
-                        if let None = board
-                            .get_piece(self.get_position().increment(Direction::Right, 2).unwrap())
-                        {
-                            let mov = Move::Castle {
-                                color: Color::Black,
-                                castle_type: Castles::KingSide,
-                            };
-                            moves.push(mov);
-                        }
-                    };
-                }
-                if board.get_modifiers().can_black_castle_queenside {
-                    if let None =
-                        board.get_piece(self.get_position().increment(Direction::Left, 1).unwrap())
-                    {
-                        if let None = board
-                            .get_piece(self.get_position().increment(Direction::Left, 2).unwrap())
-                        {
-                            if let None = board.get_piece(
-                                self.get_position().increment(Direction::Left, 3).unwrap(),
-                            ) {
-                                let mov = Move::Castle {
-                                    color: Color::Black,
-                                    castle_type: Castles::QueenSide,
-                                };
-                                moves.push(mov);
-                            }
-                        }
-                    };
-                }
-            }
+        moves
+    }
+
+    /// Returns true if the king may castle to the given side:
+    /// the right was not lost, the king and the rook stand on their starting squares,
+    /// every square between them is empty, and the king is not in check,
+    /// does not pass through an attacked square and does not end up in check
+    fn can_castle(&self, board: &Board, castle_type: Castles) -> bool {
+        let allowed = board
+            .get_modifiers()
+            .castling_rights
+            .has(self.get_color(), castle_type);
+        let rank = match self.get_color() {
+            Color::White => 0,
+            Color::Black => 7,
+        };
+        if !allowed || self.get_position() != Position::new(4, rank) {
+            return false;
         }
 
-        moves
+        // the files between the king and the rook, and the files the king crosses and lands on
+        let (rook_file, between, path): (u8, &[u8], [u8; 2]) = match castle_type {
+            Castles::KingSide => (7, &[5, 6], [5, 6]),
+            Castles::QueenSide => (0, &[1, 2, 3], [3, 2]),
+        };
+
+        let rook = board.get_piece(Position::new(rook_file, rank));
+        if rook.map(|rook| (rook.get_color(), rook.get_piece_type()))
+            != Some((self.get_color(), Pieces::Rook))
+        {
+            return false;
+        }
+
+        if between
+            .iter()
+            .any(|file| board.get_piece(Position::new(*file, rank)).is_some())
+        {
+            return false;
+        }
+
+        let opponent = self.get_color().opponent();
+        !board.is_attacked(self.get_position(), opponent)
+            && path
+                .iter()
+                .all(|file| !board.is_attacked(Position::new(*file, rank), opponent))
     }
 }
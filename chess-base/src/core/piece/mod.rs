@@ -5,36 +5,26 @@ mod pawn;
 mod queen;
 mod rook;
 
+use super::bitboard::Squares;
 use super::color::Color;
 use super::mov::Move;
 use super::pieces::Pieces;
 use super::position::Position;
 use crate::board::Board;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Piece {
     position: Position,
     color: Color,
     piece_type: Pieces,
-    value: u8,
 }
 
 impl Piece {
     pub fn new(position: Position, color: Color, piece_type: Pieces) -> Piece {
-        let value = match piece_type {
-            Pieces::King => 0,
-            Pieces::Queen => 9,
-            Pieces::Rook => 5,
-            Pieces::Bishop => 3,
-            Pieces::Knight => 3,
-            Pieces::Pawn => 1,
-        };
-
         Piece {
             position,
             color,
             piece_type,
-            value,
         }
     }
 
@@ -54,6 +44,16 @@ impl Piece {
         self.piece_type
     }
 
+    /// Returns a normal move from the position of the piece to every square of the bitboard
+    fn moves_to(&self, targets: u64) -> Vec<Move> {
+        Squares(targets)
+            .map(|index| Move::Normal {
+                from: self.position,
+                to: Position::from_index(index),
+            })
+            .collect()
+    }
+
     pub fn get_all_legal_moves(&self, board: &Board) -> Vec<Move> {
         match self.piece_type {
             Pieces::King => self.get_all_legal_moves_king(board),
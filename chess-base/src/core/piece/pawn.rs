@@ -27,22 +27,21 @@ impl Piece {
             y = 6;
         }
 
-        if let Some(ep) = board.get_modifiers().en_passant {
-            if let Some(target) = start.increment(diag1, 1) {
-                if target == ep {
-                    let mov = Move::Normal {
-                        from: start,
-                        to: target,
-                    };
-                    moves.push(mov);
-                }
-            }
-
-            if let Some(target) = start.increment(diag2, 1) {
-                if target == ep {
-                    let mov = Move::Normal {
+        // the en passant square belongs to the side to move, the other side can not capture there,
+        // and there has to be a pawn of the other side next to this one to take
+        let en_passant = board.get_modifiers().en_passant.filter(|ep| {
+            let captured = board.get_piece(Position::new(ep.get_x(), start.get_y()));
+            board.get_turn() == self.get_color()
+                && captured.is_some_and(|piece| {
+                    piece.get_piece_type() == Pieces::Pawn && piece.get_color() != self.get_color()
+                })
+        });
+        if let Some(ep) = en_passant {
+            for diag in [diag1, diag2] {
+                if start.increment(diag, 1) == Some(ep) {
+                    let mov = Move::EnPassant {
                         from: start,
-                        to: target,
+                        to: ep,
                     };
                     moves.push(mov);
                 }
@@ -145,7 +144,7 @@ impl Piece {
 
         if let Some(target) = start.increment(vertical, 1) {
             if target.get_y() != 0 && target.get_y() != 7 {
-                if let None = board.get_piece(target) {
+                if board.get_piece(target).is_none() {
                     let mov = Move::Normal {
                         from: start,
                         to: target,
@@ -155,7 +154,7 @@ impl Piece {
                     return moves;
                 }
             } else {
-                if let None = board.get_piece(target) {
+                if board.get_piece(target).is_none() {
                     let mov = Move::Promotion {
                         from: start,
                         to: target,
@@ -191,7 +190,7 @@ impl Piece {
 
         if y == self.position.get_y() {
             if let Some(target) = start.increment(vertical, 2) {
-                if let None = board.get_piece(target) {
+                if board.get_piece(target).is_none() {
                     let mov = Move::Normal {
                         from: start,
                         to: target,
@@ -1,43 +1,14 @@
 use super::*;
 use crate::board::Board;
-use crate::core::direction::Direction;
+use crate::core::attacks;
 use crate::core::mov::Move;
 
 impl Piece {
     pub fn get_all_legal_moves_rook(&self, board: &Board) -> Vec<Move> {
-        let start = self.position;
-        let directions = vec![
-            Direction::Up,
-            Direction::Right,
-            Direction::Down,
-            Direction::Left,
-        ];
-        let mut moves = Vec::new();
+        let bitboards = board.get_bitboards();
+        let targets = attacks::rook_attacks(self.position.to_index(), bitboards.all())
+            & !bitboards.color(self.get_color());
 
-        for direction in directions {
-            let mut i = 1;
-            while let Some(target) = start.increment(direction, i) {
-                if let Some(piece) = board.get_piece(target) {
-                    if piece.get_color() != self.get_color() {
-                        let mov = Move::Normal {
-                            from: start,
-                            to: target,
-                        };
-                        moves.push(mov);
-                    }
-                    break;
-                }
-
-                let mov = Move::Normal {
-                    from: start,
-                    to: target,
-                };
-                moves.push(mov);
-
-                i += 1;
-            }
-        }
-
-        moves
+        self.moves_to(targets)
     }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy,PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Pieces {
     Pawn,
     Knight,
@@ -8,12 +8,11 @@ pub enum Pieces {
     King,
 }
 
-
 impl std::fmt::Display for Pieces {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Pieces::Pawn => write!(f, "P"),
-            Pieces::Knight => write!(f, "Kn"),
+            Pieces::Knight => write!(f, "N"),
             Pieces::Bishop => write!(f, "B"),
             Pieces::Rook => write!(f, "R"),
             Pieces::Queen => write!(f, "Q"),
@@ -18,7 +18,7 @@ use std::fmt;
 /// # Examples
 ///
 /// ```
-/// use chess::positions::position::Position;
+/// use chess_base::core::position::Position;
 ///
 /// let position = Position::from_an('a', 1);
 /// assert_eq!(position, Position::new(0, 0));
@@ -43,6 +43,18 @@ impl Position {
         Position::new((file as u8) - 97, rank - 1)
     }
 
+    /// Creates a new Position struct from a square index
+    /// a1 has index 0, h1 has index 7 and h8 has index 63
+    pub fn from_index(index: usize) -> Position {
+        Position::new((index % 8) as u8, (index / 8) as u8)
+    }
+
+    /// Returns the index of the square, used to address the bits of a bitboard
+    /// a1 has index 0, h1 has index 7 and h8 has index 63
+    pub fn to_index(&self) -> usize {
+        self.y as usize * 8 + self.x as usize
+    }
+
     /// Returns the x coordinate of the position
     pub fn get_x(&self) -> u8 {
         self.x
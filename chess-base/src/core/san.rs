@@ -0,0 +1,292 @@
+// This module contains the conversion of moves from and to standard algebraic notation
+
+use super::castles::Castles;
+use super::mov::Move;
+use super::pieces::Pieces;
+use super::position::Position;
+use crate::board::Board;
+use std::fmt;
+
+/// The reason a move in standard algebraic notation could not be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SanError {
+    /// The text is not a move in standard algebraic notation
+    InvalidSyntax,
+    /// None of the legal moves in the position matches the text
+    IllegalMove,
+    /// More than one legal move matches, the text has to say which piece moves
+    AmbiguousMove,
+}
+
+impl fmt::Display for SanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SanError::InvalidSyntax => write!(f, "not a move in standard algebraic notation"),
+            SanError::IllegalMove => write!(f, "the move is not legal in this position"),
+            SanError::AmbiguousMove => {
+                write!(f, "more than one piece can make the move, say which one")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SanError {}
+
+impl Move {
+    /// Returns the move in standard algebraic notation, like Nbd7, exd5, e8=Q+ or O-O.
+    /// The board is the position before the move, and the move is expected to be legal in it
+    pub fn to_san(&self, board: &Board) -> String {
+        let mut san = match *self {
+            Move::Castle {
+                castle_type: Castles::KingSide,
+                ..
+            } => String::from("O-O"),
+            Move::Castle {
+                castle_type: Castles::QueenSide,
+                ..
+            } => String::from("O-O-O"),
+            Move::Normal { from, to }
+            | Move::Promotion { from, to, .. }
+            | Move::EnPassant { from, to } => {
+                let piece_type = board.get_piece(from).unwrap().get_piece_type();
+                let is_capture =
+                    board.get_piece(to).is_some() || matches!(self, Move::EnPassant { .. });
+
+                let mut san = String::new();
+                if piece_type == Pieces::Pawn {
+                    if is_capture {
+                        san.push(file_char(from));
+                    }
+                } else {
+                    san.push(piece_char(piece_type));
+                    san.push_str(&disambiguation(board, piece_type, from, to));
+                }
+
+                if is_capture {
+                    san.push('x');
+                }
+                san.push_str(&to.to_string());
+
+                if let Move::Promotion { promotion, .. } = self {
+                    san.push('=');
+                    san.push(piece_char(*promotion));
+                }
+
+                san
+            }
+        };
+
+        let mut after = board.clone();
+        after.make_move_no_checks(*self);
+        let turn = after.get_turn();
+        if after.is_in_check(turn) {
+            if after.get_all_legal_moves_for(turn).is_empty() {
+                san.push('#');
+            } else {
+                san.push('+');
+            }
+        }
+
+        san
+    }
+
+    /// Reads a move in standard algebraic notation for the side to move on the board.
+    /// Check and mate signs and annotations like ! and ? are allowed but not required,
+    /// castling can be written with letter O or digit zero,
+    /// and the piece letters of a promotion may leave out the = sign
+    pub fn from_san(san: &str, board: &Board) -> Result<Move, SanError> {
+        let san = san
+            .trim()
+            .trim_end_matches(['+', '#', '!', '?'])
+            .trim_end_matches(" e.p.");
+        let legal_moves = board.get_all_legal_moves_for(board.get_turn());
+
+        let castle_type = match san {
+            "O-O" | "0-0" => Some(Castles::KingSide),
+            "O-O-O" | "0-0-0" => Some(Castles::QueenSide),
+            _ => None,
+        };
+        if let Some(castle_type) = castle_type {
+            let mov = Move::Castle {
+                color: board.get_turn(),
+                castle_type,
+            };
+            if !legal_moves.contains(&mov) {
+                return Err(SanError::IllegalMove);
+            }
+            return Ok(mov);
+        }
+
+        let pattern = SanPattern::parse(san).ok_or(SanError::InvalidSyntax)?;
+
+        let mut matches = legal_moves
+            .into_iter()
+            .filter(|mov| pattern.matches(mov, board));
+        let mov = matches.next().ok_or(SanError::IllegalMove)?;
+        if matches.next().is_some() {
+            return Err(SanError::AmbiguousMove);
+        }
+
+        Ok(mov)
+    }
+}
+
+/// The parts of a non castling move written in standard algebraic notation
+struct SanPattern {
+    piece_type: Pieces,
+    from_file: Option<u8>,
+    from_rank: Option<u8>,
+    to: Position,
+    promotion: Option<Pieces>,
+}
+
+impl SanPattern {
+    /// Splits the text into its parts, or returns None if it is not a valid move
+    fn parse(san: &str) -> Option<SanPattern> {
+        let mut chars: Vec<char> = san.chars().collect();
+
+        let piece_type = match chars.first() {
+            Some('N') => Pieces::Knight,
+            Some('B') => Pieces::Bishop,
+            Some('R') => Pieces::Rook,
+            Some('Q') => Pieces::Queen,
+            Some('K') => Pieces::King,
+            _ => Pieces::Pawn,
+        };
+        if piece_type != Pieces::Pawn {
+            chars.remove(0);
+        }
+
+        // the promotion comes last, with or without the = sign
+        let promotion = match chars.last() {
+            Some(c) if c.is_ascii_uppercase() => {
+                let promotion = match c {
+                    'N' => Pieces::Knight,
+                    'B' => Pieces::Bishop,
+                    'R' => Pieces::Rook,
+                    'Q' => Pieces::Queen,
+                    _ => return None,
+                };
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(promotion)
+            }
+            _ => None,
+        };
+        if promotion.is_some() && piece_type != Pieces::Pawn {
+            return None;
+        }
+
+        // the target square is always written in full
+        let rank = chars.pop().and_then(rank_of)?;
+        let file = chars.pop().and_then(file_of)?;
+        let to = Position::new(file, rank);
+
+        if chars.last() == Some(&'x') {
+            chars.pop();
+        }
+
+        // what is left tells which of the pieces moves
+        let (from_file, from_rank) = match chars[..] {
+            [] => (None, None),
+            [c] => match (file_of(c), rank_of(c)) {
+                (Some(file), _) => (Some(file), None),
+                (_, Some(rank)) => (None, Some(rank)),
+                _ => return None,
+            },
+            [file, rank] => (Some(file_of(file)?), Some(rank_of(rank)?)),
+            _ => return None,
+        };
+
+        Some(SanPattern {
+            piece_type,
+            from_file,
+            from_rank,
+            to,
+            promotion,
+        })
+    }
+
+    /// Returns true if the legal move is the one the text describes
+    fn matches(&self, mov: &Move, board: &Board) -> bool {
+        let (from, to, promotion) = match *mov {
+            Move::Normal { from, to } | Move::EnPassant { from, to } => (from, to, None),
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => (from, to, Some(promotion)),
+            Move::Castle { .. } => return false,
+        };
+
+        let piece_type = board.get_piece(from).unwrap().get_piece_type();
+
+        // a pawn that does not capture stays on its file, which is never written
+        let from_file = match self.from_file {
+            None if piece_type == Pieces::Pawn => Some(to.get_x()),
+            from_file => from_file,
+        };
+
+        piece_type == self.piece_type
+            && to == self.to
+            && promotion == self.promotion
+            && from_file.is_none_or(|file| file == from.get_x())
+            && self.from_rank.is_none_or(|rank| rank == from.get_y())
+    }
+}
+
+/// Returns the file and or rank that set the move apart from the moves of other pieces
+/// of the same type to the same square, as short as possible
+fn disambiguation(board: &Board, piece_type: Pieces, from: Position, to: Position) -> String {
+    let others: Vec<Position> = board
+        .get_all_legal_moves_for(board.get_turn())
+        .into_iter()
+        .filter_map(|mov| match mov {
+            Move::Normal {
+                from: other,
+                to: other_to,
+            } if other_to == to && other != from => Some(other),
+            _ => None,
+        })
+        .filter(|other| board.get_piece(*other).unwrap().get_piece_type() == piece_type)
+        .collect();
+
+    if others.is_empty() {
+        String::new()
+    } else if others.iter().all(|other| other.get_x() != from.get_x()) {
+        file_char(from).to_string()
+    } else if others.iter().all(|other| other.get_y() != from.get_y()) {
+        rank_char(from).to_string()
+    } else {
+        from.to_string()
+    }
+}
+
+fn piece_char(piece_type: Pieces) -> char {
+    match piece_type {
+        Pieces::Pawn => 'P',
+        Pieces::Knight => 'N',
+        Pieces::Bishop => 'B',
+        Pieces::Rook => 'R',
+        Pieces::Queen => 'Q',
+        Pieces::King => 'K',
+    }
+}
+
+fn file_char(position: Position) -> char {
+    (b'a' + position.get_x()) as char
+}
+
+fn rank_char(position: Position) -> char {
+    (b'1' + position.get_y()) as char
+}
+
+fn file_of(c: char) -> Option<u8> {
+    ('a'..='h').contains(&c).then(|| c as u8 - b'a')
+}
+
+fn rank_of(c: char) -> Option<u8> {
+    ('1'..='8').contains(&c).then(|| c as u8 - b'1')
+}
@@ -0,0 +1,100 @@
+// This module contains the conversion of moves from and to the UCI coordinate format
+
+use super::castles::Castles;
+use super::color::Color;
+use super::mov::Move;
+use super::pieces::Pieces;
+use super::position::Position;
+use std::fmt;
+use std::str::FromStr;
+
+/// The reason a move in the UCI coordinate format could not be read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMoveError {
+    /// The text is not two squares, optionally followed by a promotion piece
+    InvalidSyntax,
+    /// A pawn can only promote to a knight, bishop, rook or queen
+    InvalidPromotion(char),
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoveError::InvalidSyntax => write!(f, "expected a move like e2e4 or e7e8q"),
+            ParseMoveError::InvalidPromotion(c) => {
+                write!(f, "a pawn can not promote to '{}'", c)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+/// Writes the move in the UCI coordinate format: the square the piece moves from,
+/// the square it moves to and the promotion piece in lowercase, like e2e4 or e7e8q.
+/// Castling is written as the move of the king, like e1g1
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Normal { from, to } | Move::EnPassant { from, to } => write!(f, "{}{}", from, to),
+            Move::Promotion {
+                from,
+                to,
+                promotion,
+            } => write!(f, "{}{}{}", from, to, promotion.to_string().to_lowercase()),
+            Move::Castle { color, castle_type } => {
+                let rank = match color {
+                    Color::White => 1,
+                    Color::Black => 8,
+                };
+                let to = match castle_type {
+                    Castles::KingSide => 'g',
+                    Castles::QueenSide => 'c',
+                };
+                write!(f, "e{}{}{}", rank, to, rank)
+            }
+        }
+    }
+}
+
+/// Reads a move in the UCI coordinate format.
+/// Without a board the text only gives the squares, so castling and en passant
+/// come out as normal moves. Board::resolve_move turns them into the move they stand for,
+/// and Board::make_move does so by itself
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Move, ParseMoveError> {
+        let chars: Vec<char> = s.trim().chars().collect();
+        if chars.len() != 4 && chars.len() != 5 {
+            return Err(ParseMoveError::InvalidSyntax);
+        }
+
+        let square = |file: char, rank: char| {
+            if ('a'..='h').contains(&file) && ('1'..='8').contains(&rank) {
+                Ok(Position::new(file as u8 - b'a', rank as u8 - b'1'))
+            } else {
+                Err(ParseMoveError::InvalidSyntax)
+            }
+        };
+        let from = square(chars[0], chars[1])?;
+        let to = square(chars[2], chars[3])?;
+
+        let Some(&promotion) = chars.get(4) else {
+            return Ok(Move::Normal { from, to });
+        };
+        let promotion = match promotion {
+            'n' => Pieces::Knight,
+            'b' => Pieces::Bishop,
+            'r' => Pieces::Rook,
+            'q' => Pieces::Queen,
+            c => return Err(ParseMoveError::InvalidPromotion(c)),
+        };
+
+        Ok(Move::Promotion {
+            from,
+            to,
+            promotion,
+        })
+    }
+}
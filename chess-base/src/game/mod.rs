@@ -0,0 +1,330 @@
+/// Game module
+/// Contains the record of a whole game: the starting position, every move that was played
+/// with its side variations, and the position the players are currently looking at
+mod node;
+
+pub use node::{GameNode, NodeId};
+
+use crate::board::{Board, DrawReason, FenError, GameStatus, MoveError, MoveOutcome, UndoInfo};
+use crate::core::color::Color;
+use crate::core::mov::Move;
+use crate::pgn::{GameResult, PgnGame, PgnMove};
+
+/// The node of the starting position
+const ROOT: NodeId = NodeId(0);
+
+/// A game as a tree of moves with a cursor in it
+/// Moving the cursor with undo, redo and goto never forgets a move,
+/// and playing a different move than the one that was played before starts a variation
+#[derive(Debug, Clone)]
+pub struct Game {
+    start: Board,
+    /// The position at the cursor
+    board: Board,
+    nodes: Vec<GameNode>,
+    /// The line undo and redo walk along, from the first move to its last move
+    line: Vec<NodeId>,
+    /// The number of moves of the line that are played on the board
+    ply: usize,
+    undos: Vec<UndoInfo>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    /// Creates a game that starts from the standard starting position
+    pub fn new() -> Game {
+        Game::from_board(Board::new_arranged())
+    }
+
+    /// Creates a game that starts from the position on the board
+    pub fn from_board(board: Board) -> Game {
+        Game {
+            start: board.clone(),
+            board,
+            nodes: vec![GameNode::root()],
+            line: Vec::new(),
+            ply: 0,
+            undos: Vec::new(),
+        }
+    }
+
+    /// Returns the position at the cursor
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the position the game started from
+    pub fn get_starting_board(&self) -> &Board {
+        &self.start
+    }
+
+    /// Returns the node of the starting position
+    pub fn root(&self) -> NodeId {
+        ROOT
+    }
+
+    /// Returns the node of the last move played on the board, or the root before the first move
+    pub fn cursor(&self) -> NodeId {
+        match self.ply {
+            0 => ROOT,
+            ply => self.line[ply - 1],
+        }
+    }
+
+    /// Returns the number of moves played on the board since the starting position
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    pub fn node(&self, id: NodeId) -> &GameNode {
+        &self.nodes[id.0]
+    }
+
+    pub fn node_mut(&mut self, id: NodeId) -> &mut GameNode {
+        &mut self.nodes[id.0]
+    }
+
+    /// Returns the line the cursor is on, from the first move to its last move
+    /// The moves after the cursor are the ones redo will play
+    pub fn current_line(&self) -> &[NodeId] {
+        &self.line
+    }
+
+    /// Returns the main line of the game, from the first move to the last one
+    pub fn main_line(&self) -> Vec<NodeId> {
+        self.continuation(ROOT)
+    }
+
+    /// Returns the moves that follow the node when the first move is always taken
+    fn continuation(&self, mut id: NodeId) -> Vec<NodeId> {
+        let mut line = Vec::new();
+        while let Some(&next) = self.node(id).children.first() {
+            line.push(next);
+            id = next;
+        }
+        line
+    }
+
+    /// Returns the moves that lead from the starting position to the node, the node included
+    fn path_to(&self, mut id: NodeId) -> Vec<NodeId> {
+        let mut path = Vec::new();
+        while let Some(parent) = self.node(id).parent {
+            path.push(id);
+            id = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// Makes a move at the cursor if it is legal and moves the cursor to it
+    /// If the move was already played from this position the existing node is reused,
+    /// otherwise it is added after the moves that are already there, as a variation
+    pub fn make_move(&mut self, mov: Move) -> Result<MoveOutcome, MoveError> {
+        let before = self.board.clone();
+        let outcome = self.board.make_move(mov)?;
+        let mov = outcome.undo.get_move();
+        self.undos.push(outcome.undo);
+
+        let parent = self.cursor();
+        let existing = self
+            .node(parent)
+            .children
+            .iter()
+            .copied()
+            .find(|&child| self.node(child).get_move() == Some(mov));
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let id = NodeId(self.nodes.len());
+                self.nodes
+                    .push(GameNode::new(mov, mov.to_san(&before), parent));
+                self.node_mut(parent).children.push(id);
+                id
+            }
+        };
+
+        self.line.truncate(self.ply);
+        self.line.push(id);
+        self.line.extend(self.continuation(id));
+        self.ply += 1;
+
+        Ok(outcome)
+    }
+
+    /// Takes back the move at the cursor
+    /// The move stays in the game and can be played again with redo
+    /// Returns false if the cursor is already at the starting position
+    pub fn undo(&mut self) -> bool {
+        let Some(undo) = self.undos.pop() else {
+            return false;
+        };
+        self.board.unmake_move(undo);
+        self.ply -= 1;
+        true
+    }
+
+    /// Plays the next move of the current line again
+    /// Returns false if the cursor is already at the end of the line
+    pub fn redo(&mut self) -> bool {
+        let Some(&next) = self.line.get(self.ply) else {
+            return false;
+        };
+        let mov = self.node(next).get_move().unwrap();
+        self.undos.push(self.board.make_move_no_checks(mov));
+        self.ply += 1;
+        true
+    }
+
+    /// Moves the cursor to the position after the given number of moves of the current line
+    /// Returns false if the line is not that long
+    pub fn goto(&mut self, ply: usize) -> bool {
+        if ply > self.line.len() {
+            return false;
+        }
+        while self.ply > ply {
+            self.undo();
+        }
+        while self.ply < ply {
+            self.redo();
+        }
+        true
+    }
+
+    /// Moves the cursor to any node of the game
+    /// The line of the node becomes the current line
+    pub fn goto_node(&mut self, id: NodeId) {
+        let path = self.path_to(id);
+        let common = path
+            .iter()
+            .zip(&self.line[..self.ply])
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.goto(common);
+
+        let ply = path.len();
+        self.line = path;
+        self.line.extend(self.continuation(id));
+        self.goto(ply);
+    }
+
+    /// Makes the variation the node is in the main line,
+    /// by putting it and all the moves before it first among their alternatives
+    pub fn promote_to_main_line(&mut self, id: NodeId) {
+        let mut id = id;
+        while let Some(parent) = self.node(id).parent {
+            let children = &mut self.node_mut(parent).children;
+            children.retain(|&child| child != id);
+            children.insert(0, id);
+            id = parent;
+        }
+    }
+
+    /// Returns the position after the move of the node
+    pub fn board_at(&self, id: NodeId) -> Board {
+        let mut board = self.start.clone();
+        for node in self.path_to(id) {
+            board.make_move_no_checks(self.node(node).get_move().unwrap());
+        }
+        board
+    }
+
+    /// Returns the state of the game at the cursor
+    pub fn status(&self) -> GameStatus {
+        self.board.status()
+    }
+
+    /// Ends the game at the cursor with a win for the opponent of the color
+    /// Taking back the move before it takes back the resignation as well
+    pub fn resign(&mut self, color: Color) {
+        self.board.resign(color)
+    }
+
+    /// Ends the game at the cursor in a draw if the side to move is allowed to claim one
+    /// Returns the reason of the draw, or None if no draw can be claimed
+    pub fn claim_draw(&mut self) -> Option<DrawReason> {
+        self.board.claim_draw()
+    }
+
+    /// Builds a game from a PGN record, with the cursor at the end of the main line
+    /// The comments of a move are joined into one
+    pub fn from_pgn(pgn: &PgnGame) -> Result<Game, FenError> {
+        let mut game = Game::from_board(pgn.starting_board()?);
+        game.add_pgn_line(ROOT, &pgn.moves);
+
+        game.line = game.main_line();
+        game.goto(game.line.len());
+        Ok(game)
+    }
+
+    fn add_pgn_line(&mut self, parent: NodeId, moves: &[PgnMove]) {
+        let mut parent = parent;
+        for pgn_move in moves {
+            let id = NodeId(self.nodes.len());
+            let mut node = GameNode::new(pgn_move.mov, pgn_move.san.clone(), parent);
+            node.time_spent = pgn_move.time_spent;
+            if !pgn_move.comments.is_empty() {
+                node.comment = Some(pgn_move.comments.join(" "));
+            }
+            self.nodes.push(node);
+            self.node_mut(parent).children.push(id);
+
+            for variation in &pgn_move.variations {
+                self.add_pgn_line(parent, variation);
+            }
+            parent = id;
+        }
+    }
+
+    /// Returns the game as a PGN record without tags
+    /// The result is the state of the game at the end of the main line
+    pub fn to_pgn(&self) -> PgnGame {
+        let mut pgn = PgnGame::new();
+        if self.start.to_fen() != Board::new_arranged().to_fen() {
+            pgn.set_tag("FEN", &self.start.to_fen());
+        }
+        pgn.moves = self.pgn_line(ROOT);
+
+        let main_line = self.main_line();
+        let status = if self.line.starts_with(&main_line) && self.ply == main_line.len() {
+            self.board.status()
+        } else {
+            self.board_at(main_line.last().copied().unwrap_or(ROOT))
+                .status()
+        };
+        pgn.result = GameResult::from(status);
+        pgn
+    }
+
+    /// Returns the moves after the node along the first moves,
+    /// with the other moves at every step as variations
+    fn pgn_line(&self, parent: NodeId) -> Vec<PgnMove> {
+        let mut moves = Vec::new();
+        let mut parent = parent;
+
+        while let Some((&first, others)) = self.node(parent).children.split_first() {
+            let mut pgn_move = self.pgn_move(first);
+            for &other in others {
+                let mut variation = vec![self.pgn_move(other)];
+                variation.extend(self.pgn_line(other));
+                pgn_move.variations.push(variation);
+            }
+            moves.push(pgn_move);
+            parent = first;
+        }
+
+        moves
+    }
+
+    fn pgn_move(&self, id: NodeId) -> PgnMove {
+        let node = self.node(id);
+        let mut pgn_move = PgnMove::new(node.get_move().unwrap(), node.get_san().to_string());
+        pgn_move.time_spent = node.time_spent;
+        pgn_move.comments.extend(node.comment.clone());
+        pgn_move
+    }
+}
@@ -0,0 +1,64 @@
+/// Node module
+/// Contains the moves stored in a game record and the handle used to point at them
+use crate::core::mov::Move;
+use std::time::Duration;
+
+/// Points at a move in the tree of a game, or at the starting position before the first move
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(pub(super) usize);
+
+/// A move of a game and what is known about it
+/// The starting position is a node as well, it is the only one without a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameNode {
+    mov: Option<Move>,
+    san: String,
+    /// How long the player thought about the move
+    pub time_spent: Option<Duration>,
+    pub comment: Option<String>,
+    pub(super) parent: Option<NodeId>,
+    pub(super) children: Vec<NodeId>,
+}
+
+impl GameNode {
+    pub(super) fn root() -> GameNode {
+        GameNode {
+            mov: None,
+            san: String::new(),
+            time_spent: None,
+            comment: None,
+            parent: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub(super) fn new(mov: Move, san: String, parent: NodeId) -> GameNode {
+        GameNode {
+            mov: Some(mov),
+            san,
+            parent: Some(parent),
+            ..GameNode::root()
+        }
+    }
+
+    /// Returns the move, or None for the starting position
+    pub fn get_move(&self) -> Option<Move> {
+        self.mov
+    }
+
+    /// Returns the move in standard algebraic notation
+    pub fn get_san(&self) -> &str {
+        &self.san
+    }
+
+    /// Returns the node of the move before this one, or None for the starting position
+    pub fn get_parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+
+    /// Returns the moves that were played after this one
+    /// The first one continues the main line, the others start variations
+    pub fn get_children(&self) -> &[NodeId] {
+        &self.children
+    }
+}
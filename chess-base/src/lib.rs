@@ -1,2 +1,4 @@
 pub mod board;
 pub mod core;
+pub mod game;
+pub mod pgn;
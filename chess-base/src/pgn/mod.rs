@@ -0,0 +1,237 @@
+/// PGN module
+/// Contains the game records read from and written to files in Portable Game Notation,
+/// the reader and writer for them and the error returned when a file is malformed
+mod reader;
+mod writer;
+
+pub use reader::read_pgn;
+pub use writer::{write_pgn, SEVEN_TAG_ROSTER};
+
+use crate::board::{Board, FenError, GameStatus};
+use crate::core::color::Color;
+use crate::core::mov::Move;
+use crate::core::san::SanError;
+use std::fmt;
+use std::time::Duration;
+
+/// The result of a game, as written at the end of its movetext
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    /// The game is still going on, or the result is not known
+    Unknown,
+}
+
+impl GameResult {
+    /// Reads a termination marker like 1-0 or 1/2-1/2
+    fn from_marker(marker: &str) -> Option<GameResult> {
+        match marker {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl From<GameStatus> for GameResult {
+    fn from(status: GameStatus) -> GameResult {
+        match status {
+            GameStatus::Ongoing => GameResult::Unknown,
+            GameStatus::Checkmate { winner } | GameStatus::Resigned { winner } => match winner {
+                Color::White => GameResult::WhiteWins,
+                Color::Black => GameResult::BlackWins,
+            },
+            GameStatus::Stalemate | GameStatus::Draw(_) => GameResult::Draw,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameResult::WhiteWins => write!(f, "1-0"),
+            GameResult::BlackWins => write!(f, "0-1"),
+            GameResult::Draw => write!(f, "1/2-1/2"),
+            GameResult::Unknown => write!(f, "*"),
+        }
+    }
+}
+
+/// A move of a game record together with its annotations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnMove {
+    pub mov: Move,
+    /// The move as it was written in the file
+    pub san: String,
+    /// Numeric annotation glyphs, $1 or ! is 1, $2 or ? is 2 and so on
+    pub nags: Vec<u8>,
+    /// Comments written before the move, only found at the start of a game or variation
+    pub comments_before: Vec<String>,
+    /// Comments written after the move
+    pub comments: Vec<String>,
+    /// The time left on the clock of the side that moved, written as a [%clk 1:30:00] comment
+    pub clock: Option<Duration>,
+    /// How long the side that moved thought about the move, written as a [%emt 0:00:12] comment
+    pub time_spent: Option<Duration>,
+    /// Other moves that could have been played instead of this one, each followed by its line
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+impl PgnMove {
+    pub fn new(mov: Move, san: String) -> PgnMove {
+        PgnMove {
+            mov,
+            san,
+            nags: Vec::new(),
+            comments_before: Vec::new(),
+            comments: Vec::new(),
+            clock: None,
+            time_spent: None,
+            variations: Vec::new(),
+        }
+    }
+}
+
+/// A single game of a PGN file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnGame {
+    /// The tag pairs in the order they were written, like ("White", "Carlsen, Magnus")
+    pub tags: Vec<(String, String)>,
+    /// The main line of the game
+    pub moves: Vec<PgnMove>,
+    pub result: GameResult,
+}
+
+impl Default for PgnGame {
+    fn default() -> Self {
+        PgnGame::new()
+    }
+}
+
+impl PgnGame {
+    /// Creates a game record without tags or moves, for a game that starts from the standard position
+    pub fn new() -> PgnGame {
+        PgnGame {
+            tags: Vec::new(),
+            moves: Vec::new(),
+            result: GameResult::Unknown,
+        }
+    }
+
+    /// Sets the value of a tag, replacing the old value if the tag is already there
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Reads the first game of a PGN file
+    pub fn from_pgn(pgn: &str) -> Result<PgnGame, PgnError> {
+        let mut games = read_pgn(pgn)?;
+        if games.is_empty() {
+            return Err(PgnError::new(1, 1, PgnErrorKind::NoGame));
+        }
+        Ok(games.swap_remove(0))
+    }
+
+    /// Returns the value of the tag with the given name
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the position the game starts from,
+    /// which is the one in the FEN tag or the standard starting position if there is none
+    pub fn starting_board(&self) -> Result<Board, FenError> {
+        match self.tag("FEN") {
+            Some(fen) => Board::from_fen(fen),
+            None => Ok(Board::new_arranged()),
+        }
+    }
+
+    /// Plays the main line onto the starting position and returns the final position.
+    /// The moves are expected to be legal, as they are in every game returned by the reader
+    pub fn replay(&self) -> Result<Board, FenError> {
+        let mut board = self.starting_board()?;
+        for mov in &self.moves {
+            board.make_move_no_checks(mov.mov);
+        }
+        Ok(board)
+    }
+}
+
+/// What exactly is wrong at the place named by a PgnError
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnErrorKind {
+    /// The input does not contain a single game
+    NoGame,
+    /// A character that can not start any token
+    UnexpectedCharacter(char),
+    /// A token that is not allowed where it was found, like a tag pair in the middle of the moves
+    UnexpectedToken(String),
+    /// The input ends inside a comment
+    UnterminatedComment,
+    /// The input ends inside a string
+    UnterminatedString,
+    /// The input ends before a variation is closed
+    UnterminatedVariation,
+    /// A tag pair is not a name followed by a string in square brackets
+    InvalidTag,
+    /// A numeric annotation glyph is not a number from 0 to 255
+    InvalidNag,
+    /// The FEN tag does not hold a valid position
+    InvalidFen(FenError),
+    /// A move that can not be played in the position it was written for
+    InvalidMove(String, SanError),
+}
+
+/// Error returned when a PGN file can not be read
+/// Line and column are counted from 1 and point at the start of the offending token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind,
+}
+
+impl PgnError {
+    fn new(line: usize, column: usize, kind: PgnErrorKind) -> PgnError {
+        PgnError { line, column, kind }
+    }
+}
+
+impl fmt::Display for PgnErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PgnErrorKind::NoGame => write!(f, "no game found"),
+            PgnErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{}'", c),
+            PgnErrorKind::UnexpectedToken(token) => write!(f, "unexpected \"{}\"", token),
+            PgnErrorKind::UnterminatedComment => write!(f, "comment is never closed"),
+            PgnErrorKind::UnterminatedString => write!(f, "string is never closed"),
+            PgnErrorKind::UnterminatedVariation => write!(f, "variation is never closed"),
+            PgnErrorKind::InvalidTag => write!(f, "expected a tag like [Event \"name\"]"),
+            PgnErrorKind::InvalidNag => write!(f, "invalid annotation glyph"),
+            PgnErrorKind::InvalidFen(error) => write!(f, "{}", error),
+            PgnErrorKind::InvalidMove(san, error) => write!(f, "move {}: {}", san, error),
+        }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid PGN at line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )
+    }
+}
+
+impl std::error::Error for PgnError {}
@@ -0,0 +1,436 @@
+/// Reader module
+/// Splits a PGN file into tokens and builds the game records from them,
+/// checking every move against the position it is played in
+use super::{GameResult, PgnError, PgnErrorKind, PgnGame, PgnMove};
+use crate::board::Board;
+use crate::core::mov::Move;
+use std::iter::Peekable;
+use std::str::Chars;
+use std::time::Duration;
+
+/// Reads all games of a PGN file.
+/// Games are separated by their result or by the tag pairs of the next game
+pub fn read_pgn(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let mut parser = Parser::new(pgn)?;
+    let mut games = Vec::new();
+
+    while parser.peek().is_some() {
+        games.push(parser.parse_game()?);
+    }
+
+    Ok(games)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    TagOpen,
+    TagClose,
+    VariationOpen,
+    VariationClose,
+    Period,
+    String(String),
+    /// A move, a move number or a tag name
+    Symbol(String),
+    Nag(u8),
+    Comment(String),
+    Result(GameResult),
+}
+
+impl Token {
+    /// The token as it could be written in the file, used in error messages
+    fn text(&self) -> String {
+        match self {
+            Token::TagOpen => String::from("["),
+            Token::TagClose => String::from("]"),
+            Token::VariationOpen => String::from("("),
+            Token::VariationClose => String::from(")"),
+            Token::Period => String::from("."),
+            Token::String(string) => format!("\"{}\"", string),
+            Token::Symbol(symbol) => symbol.clone(),
+            Token::Nag(nag) => format!("${}", nag),
+            Token::Comment(comment) => format!("{{{}}}", comment),
+            Token::Result(result) => result.to_string(),
+        }
+    }
+}
+
+/// A token and the line and column it starts at
+#[derive(Debug, Clone)]
+struct Spanned {
+    token: Token,
+    line: usize,
+    column: usize,
+}
+
+impl Spanned {
+    fn error(&self, kind: PgnErrorKind) -> PgnError {
+        PgnError::new(self.line, self.column, kind)
+    }
+
+    fn unexpected(&self) -> PgnError {
+        self.error(PgnErrorKind::UnexpectedToken(self.token.text()))
+    }
+}
+
+/// Walks over the characters of the input and keeps track of the line and column
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Lexer<'a> {
+        Lexer {
+            chars: input.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    /// Consumes characters up to and including the end of the line
+    fn skip_line(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+            text.push(c);
+        }
+        text
+    }
+
+    fn tokenize(mut self) -> Result<Vec<Spanned>, PgnError> {
+        let mut tokens = Vec::new();
+
+        while let Some(c) = self.peek() {
+            let (line, column) = (self.line, self.column);
+            let error = |kind| PgnError::new(line, column, kind);
+
+            let token = match c {
+                _ if c.is_whitespace() => {
+                    self.bump();
+                    continue;
+                }
+                // a line starting with % is meant for other programs
+                '%' if column == 1 => {
+                    self.skip_line();
+                    continue;
+                }
+                '[' | ']' | '(' | ')' | '.' | '*' => {
+                    self.bump();
+                    match c {
+                        '[' => Token::TagOpen,
+                        ']' => Token::TagClose,
+                        '(' => Token::VariationOpen,
+                        ')' => Token::VariationClose,
+                        '.' => Token::Period,
+                        _ => Token::Result(GameResult::Unknown),
+                    }
+                }
+                ';' => {
+                    self.bump();
+                    Token::Comment(self.skip_line().trim().to_string())
+                }
+                '{' => {
+                    self.bump();
+                    let mut comment = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('}') => break,
+                            Some(c) => comment.push(c),
+                            None => return Err(error(PgnErrorKind::UnterminatedComment)),
+                        }
+                    }
+                    Token::Comment(comment.trim().to_string())
+                }
+                '"' => {
+                    self.bump();
+                    let mut string = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('"') => break,
+                            Some('\\') => match self.bump() {
+                                Some(c) => string.push(c),
+                                None => return Err(error(PgnErrorKind::UnterminatedString)),
+                            },
+                            Some('\n') | None => {
+                                return Err(error(PgnErrorKind::UnterminatedString))
+                            }
+                            Some(c) => string.push(c),
+                        }
+                    }
+                    Token::String(string)
+                }
+                '$' => {
+                    self.bump();
+                    let mut digits = String::new();
+                    while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+                        self.bump();
+                        digits.push(c);
+                    }
+                    let nag = digits
+                        .parse()
+                        .map_err(|_| error(PgnErrorKind::InvalidNag))?;
+                    Token::Nag(nag)
+                }
+                '!' | '?' => {
+                    let mut suffix = String::new();
+                    while let Some(c) = self.peek().filter(|c| matches!(c, '!' | '?')) {
+                        self.bump();
+                        suffix.push(c);
+                    }
+                    let nag = match suffix.as_str() {
+                        "!" => 1,
+                        "?" => 2,
+                        "!!" => 3,
+                        "??" => 4,
+                        "!?" => 5,
+                        "?!" => 6,
+                        _ => return Err(error(PgnErrorKind::InvalidNag)),
+                    };
+                    Token::Nag(nag)
+                }
+                _ if c.is_ascii_alphanumeric() => {
+                    let mut symbol = String::new();
+                    while let Some(c) = self
+                        .peek()
+                        .filter(|c| c.is_ascii_alphanumeric() || "_+#=:-/".contains(*c))
+                    {
+                        self.bump();
+                        symbol.push(c);
+                    }
+                    match GameResult::from_marker(&symbol) {
+                        Some(result) => Token::Result(result),
+                        None => Token::Symbol(symbol),
+                    }
+                }
+                _ => return Err(error(PgnErrorKind::UnexpectedCharacter(c))),
+            };
+
+            tokens.push(Spanned {
+                token,
+                line,
+                column,
+            });
+        }
+
+        Ok(tokens)
+    }
+}
+
+struct Parser {
+    tokens: Vec<Spanned>,
+    index: usize,
+}
+
+impl Parser {
+    fn new(pgn: &str) -> Result<Parser, PgnError> {
+        let tokens = Lexer::new(pgn).tokenize()?;
+        Ok(Parser { tokens, index: 0 })
+    }
+
+    fn peek(&self) -> Option<&Spanned> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Spanned> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn parse_game(&mut self) -> Result<PgnGame, PgnError> {
+        let mut tags = Vec::new();
+        let mut board = Board::new_arranged();
+
+        while self.peek().is_some_and(|next| next.token == Token::TagOpen) {
+            let open = self.next().unwrap();
+            let (name, value) = match (self.next(), self.next(), self.next()) {
+                (
+                    Some(Spanned {
+                        token: Token::Symbol(name),
+                        ..
+                    }),
+                    Some(Spanned {
+                        token: Token::String(value),
+                        ..
+                    }),
+                    Some(Spanned {
+                        token: Token::TagClose,
+                        ..
+                    }),
+                ) => (name, value),
+                _ => return Err(open.error(PgnErrorKind::InvalidTag)),
+            };
+
+            if name == "FEN" {
+                board = Board::from_fen(&value)
+                    .map_err(|error| open.error(PgnErrorKind::InvalidFen(error)))?;
+            }
+            tags.push((name, value));
+        }
+
+        let (moves, result) = self.parse_line(board, None)?;
+
+        let result = result
+            .or_else(|| {
+                let (_, value) = tags.iter().find(|(name, _)| name == "Result")?;
+                GameResult::from_marker(value)
+            })
+            .unwrap_or(GameResult::Unknown);
+
+        Ok(PgnGame {
+            tags,
+            moves,
+            result,
+        })
+    }
+
+    /// Parses the moves of the main line, or of a variation if the opening parenthesis is given.
+    /// The board is the position before the first move of the line.
+    /// The result is returned if the main line ends with one
+    fn parse_line(
+        &mut self,
+        mut board: Board,
+        open: Option<&Spanned>,
+    ) -> Result<(Vec<PgnMove>, Option<GameResult>), PgnError> {
+        let mut moves: Vec<PgnMove> = Vec::new();
+        // the position before the last move, which is where its variations start
+        let mut previous: Option<Board> = None;
+        let mut comments_before = Vec::new();
+
+        loop {
+            let Some(next) = self.peek().cloned() else {
+                return match open {
+                    Some(open) => Err(open.error(PgnErrorKind::UnterminatedVariation)),
+                    None => Ok((moves, None)),
+                };
+            };
+
+            match &next.token {
+                // the tag pairs of the next game
+                Token::TagOpen if open.is_none() => return Ok((moves, None)),
+                Token::Result(result) if open.is_none() => {
+                    self.next();
+                    return Ok((moves, Some(*result)));
+                }
+                Token::VariationClose if open.is_some() => {
+                    self.next();
+                    return Ok((moves, None));
+                }
+                Token::Period => {
+                    self.next();
+                }
+                Token::Symbol(symbol) if symbol.chars().all(|c| c.is_ascii_digit()) => {
+                    self.next();
+                }
+                Token::Symbol(san) => {
+                    self.next();
+                    let mov = Move::from_san(san, &board).map_err(|error| {
+                        next.error(PgnErrorKind::InvalidMove(san.clone(), error))
+                    })?;
+
+                    previous = Some(board.clone());
+                    board.make_move_no_checks(mov);
+
+                    let mut pgn_move = PgnMove::new(mov, san.clone());
+                    pgn_move.comments_before = std::mem::take(&mut comments_before);
+                    moves.push(pgn_move);
+                }
+                Token::Nag(nag) => {
+                    self.next();
+                    match moves.last_mut() {
+                        Some(last) => last.nags.push(*nag),
+                        None => return Err(next.unexpected()),
+                    }
+                }
+                Token::Comment(comment) => {
+                    self.next();
+                    match moves.last_mut() {
+                        Some(last) => {
+                            let (clock, comment) = split_time(comment, "clk");
+                            let (time_spent, comment) = split_time(&comment, "emt");
+                            if clock.is_some() {
+                                last.clock = clock;
+                            }
+                            if time_spent.is_some() {
+                                last.time_spent = time_spent;
+                            }
+                            if !comment.is_empty() {
+                                last.comments.push(comment);
+                            }
+                        }
+                        None => comments_before.push(comment.clone()),
+                    }
+                }
+                Token::VariationOpen => {
+                    self.next();
+                    let (Some(last), Some(previous)) = (moves.last_mut(), &previous) else {
+                        return Err(next.unexpected());
+                    };
+                    let (variation, _) = self.parse_line(previous.clone(), Some(&next))?;
+                    last.variations.push(variation);
+                }
+                _ => return Err(next.unexpected()),
+            }
+        }
+    }
+}
+
+/// Takes a command with a time, like [%clk 1:30:00] or [%emt 0:00:12], out of a comment.
+/// Returns the time and what is left of the comment
+fn split_time(comment: &str, command: &str) -> (Option<Duration>, String) {
+    let command = format!("[%{} ", command);
+    let Some(start) = comment.find(&command) else {
+        return (None, comment.to_string());
+    };
+    let Some(length) = comment[start..].find(']') else {
+        return (None, comment.to_string());
+    };
+
+    let time = &comment[start + command.len()..start + length];
+    let mut parts = time.trim().split(':');
+    let clock = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(hours), Some(minutes), Some(seconds), None) => {
+            match (
+                hours.parse::<u64>(),
+                minutes.parse::<u64>(),
+                seconds.parse::<f64>(),
+            ) {
+                // times that do not fit in a Duration are not a clock, the comment is kept as it is
+                (Ok(hours), Ok(minutes), Ok(seconds)) => hours
+                    .checked_mul(3600)
+                    .and_then(|hours| hours.checked_add(minutes.checked_mul(60)?))
+                    .map(Duration::from_secs)
+                    .zip(Duration::try_from_secs_f64(seconds).ok())
+                    .and_then(|(whole, seconds)| whole.checked_add(seconds)),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+
+    match clock {
+        Some(clock) => {
+            let rest = format!("{} {}", &comment[..start], &comment[start + length + 1..]);
+            (Some(clock), rest.trim().to_string())
+        }
+        None => (None, comment.to_string()),
+    }
+}
@@ -0,0 +1,176 @@
+/// Writer module
+/// Turns game records back into PGN in the export format other programs expect:
+/// the seven tag roster first and the movetext wrapped to lines of at most 79 characters
+use super::{PgnGame, PgnMove};
+use crate::core::color::Color;
+use std::time::Duration;
+
+/// The tags every exported game starts with, in this order
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+const LINE_WIDTH: usize = 79;
+
+/// Writes all games into one PGN file, separated by an empty line
+pub fn write_pgn(games: &[PgnGame]) -> String {
+    games
+        .iter()
+        .map(PgnGame::to_pgn)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl PgnGame {
+    /// Returns the game in PGN.
+    /// Tags of the seven tag roster that are missing are written as unknown,
+    /// and the Result tag always matches the result of the game
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+        for (name, value) in self.export_tags() {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+        }
+        pgn.push('\n');
+
+        // the move numbers continue from the position in the FEN tag
+        let ply = match self.starting_board() {
+            Ok(board) => {
+                let fullmove = board.get_modifiers().fullmove_number as usize;
+                (fullmove - 1) * 2 + usize::from(board.get_turn() == Color::Black)
+            }
+            Err(_) => 0,
+        };
+
+        let mut tokens = Vec::new();
+        write_line(&self.moves, ply, &mut tokens);
+        tokens.push(self.result.to_string());
+
+        pgn.push_str(&wrap(&tokens));
+        pgn.push('\n');
+        pgn
+    }
+
+    fn export_tags(&self) -> Vec<(&str, String)> {
+        let mut tags: Vec<(&str, String)> = SEVEN_TAG_ROSTER
+            .iter()
+            .map(|&name| {
+                let value = match name {
+                    "Result" => self.result.to_string(),
+                    "Date" => self.tag(name).unwrap_or("????.??.??").to_string(),
+                    _ => self.tag(name).unwrap_or("?").to_string(),
+                };
+                (name, value)
+            })
+            .collect();
+
+        for (name, value) in &self.tags {
+            if SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+                continue;
+            }
+            // readers only look at the FEN tag if SetUp says so
+            if name == "FEN" && self.tag("SetUp").is_none() {
+                tags.push(("SetUp", String::from("1")));
+            }
+            tags.push((name, value.clone()));
+        }
+
+        tags
+    }
+}
+
+/// Adds the tokens of a line of moves, where ply is the number of half moves played before it.
+/// Black moves get their own move number at the start of a line
+/// and after anything that comes between them and the white move
+fn write_line(moves: &[PgnMove], ply: usize, tokens: &mut Vec<String>) {
+    let mut needs_number = true;
+
+    for (i, mov) in moves.iter().enumerate() {
+        let ply = ply + i;
+        let number = ply / 2 + 1;
+
+        for comment in &mov.comments_before {
+            push_comment(comment, tokens);
+        }
+
+        // the number stays on the same line as its move
+        if ply.is_multiple_of(2) {
+            tokens.push(format!("{}. {}", number, mov.san));
+        } else if needs_number || !mov.comments_before.is_empty() {
+            tokens.push(format!("{}... {}", number, mov.san));
+        } else {
+            tokens.push(mov.san.clone());
+        }
+
+        for nag in &mov.nags {
+            tokens.push(format!("${}", nag));
+        }
+        if let Some(clock) = mov.clock {
+            push_comment(&format!("[%clk {}]", format_time(clock)), tokens);
+        }
+        if let Some(time_spent) = mov.time_spent {
+            push_comment(&format!("[%emt {}]", format_time(time_spent)), tokens);
+        }
+        for comment in &mov.comments {
+            push_comment(comment, tokens);
+        }
+
+        for variation in &mov.variations {
+            let start = tokens.len();
+            write_line(variation, ply, tokens);
+            if tokens.len() > start {
+                tokens[start].insert(0, '(');
+                tokens.last_mut().unwrap().push(')');
+            }
+        }
+
+        needs_number = mov.clock.is_some()
+            || mov.time_spent.is_some()
+            || !mov.comments.is_empty()
+            || !mov.variations.is_empty();
+    }
+}
+
+/// Adds a comment word by word, so long comments can be spread over several lines
+/// A closing brace would end the comment early, so braces in the text are left out
+fn push_comment(comment: &str, tokens: &mut Vec<String>) {
+    let comment = format!("{{{}}}", comment.replace('}', ""));
+    tokens.extend(comment.split_whitespace().map(String::from));
+}
+
+/// Writes the time as hours, minutes and seconds, like 1:05:09,
+/// with tenths of a second only when there are any
+fn format_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    let tenths = time.subsec_millis() / 100;
+    let time = format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    if tenths > 0 {
+        format!("{}.{}", time, tenths)
+    } else {
+        time
+    }
+}
+
+/// Joins the tokens with spaces and breaks the lines before they get too long
+fn wrap(tokens: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+
+    for word in tokens {
+        if !line.is_empty() && line.len() + 1 + word.len() > LINE_WIDTH {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    lines.push(line);
+
+    lines.join("\n")
+}
@@ -0,0 +1,68 @@
+use chess_base::core::attacks;
+use chess_base::core::color::Color;
+
+/// Walks from the square in every direction until the edge of the board or an occupied square
+fn walk(index: usize, occupancy: u64, directions: &[(i32, i32)]) -> u64 {
+    let mut attacks = 0;
+    for (dx, dy) in directions {
+        let mut x = (index % 8) as i32 + dx;
+        let mut y = (index / 8) as i32 + dy;
+        while (0..8).contains(&x) && (0..8).contains(&y) {
+            let bit = 1 << (y * 8 + x);
+            attacks |= bit;
+            if occupancy & bit != 0 {
+                break;
+            }
+            x += dx;
+            y += dy;
+        }
+    }
+    attacks
+}
+
+#[test]
+fn sliding_attacks_match_ray_walks() {
+    let rook = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+    let bishop = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+    // xorshift generator, so the occupancies are the same on every run
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut random = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    for index in 0..64 {
+        for _ in 0..200 {
+            let occupancy = random() & random();
+
+            assert_eq!(
+                attacks::rook_attacks(index, occupancy),
+                walk(index, occupancy, &rook)
+            );
+            assert_eq!(
+                attacks::bishop_attacks(index, occupancy),
+                walk(index, occupancy, &bishop)
+            );
+        }
+    }
+}
+
+#[test]
+fn leaper_attacks_stay_on_the_board() {
+    // knight on a1 reaches b3 and c2, king on h8 reaches g8, g7 and h7
+    assert_eq!(attacks::knight_attacks(0), (1 << 17) | (1 << 10));
+    assert_eq!(attacks::king_attacks(63), (1 << 62) | (1 << 54) | (1 << 55));
+
+    // pawns on e4 attack d5 and f5 for white, d3 and f3 for black
+    assert_eq!(
+        attacks::pawn_attacks(28, Color::White),
+        (1 << 35) | (1 << 37)
+    );
+    assert_eq!(
+        attacks::pawn_attacks(28, Color::Black),
+        (1 << 19) | (1 << 21)
+    );
+}
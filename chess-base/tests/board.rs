@@ -0,0 +1,225 @@
+mod common;
+
+use chess_base::board::{Board, MoveError};
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use common::normal;
+
+/// Checks that every piece on the board is set in exactly the bitboards it belongs to
+fn assert_bitboards_match(board: &Board) {
+    let bitboards = board.get_bitboards();
+    let pieces = [
+        Pieces::Pawn,
+        Pieces::Knight,
+        Pieces::Bishop,
+        Pieces::Rook,
+        Pieces::Queen,
+        Pieces::King,
+    ];
+
+    for index in 0..64 {
+        let position = Position::from_index(index);
+        let bit = 1 << index;
+
+        for color in [Color::White, Color::Black] {
+            for piece_type in pieces {
+                let expected = board
+                    .get_piece(position)
+                    .map(|piece| piece.get_color() == color && piece.get_piece_type() == piece_type)
+                    .unwrap_or(false);
+                assert_eq!(bitboards.pieces(color, piece_type) & bit != 0, expected);
+            }
+        }
+
+        assert_eq!(
+            bitboards.all() & bit != 0,
+            board.get_piece(position).is_some()
+        );
+    }
+}
+
+#[test]
+fn bitboards_follow_the_pieces() {
+    let mut board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    assert_bitboards_match(&board);
+
+    let moves = [
+        Move::Normal {
+            from: Position::from_an('e', 5),
+            to: Position::from_an('f', 7),
+        },
+        Move::Normal {
+            from: Position::from_an('e', 8),
+            to: Position::from_an('f', 7),
+        },
+        Move::Normal {
+            from: Position::from_an('d', 5),
+            to: Position::from_an('e', 6),
+        },
+    ];
+
+    for mov in moves {
+        board.make_move(mov).unwrap();
+        assert_bitboards_match(&board);
+    }
+
+    assert_eq!(board.get_all_pieces().len(), 29);
+}
+
+#[test]
+fn square_indices_round_trip() {
+    for index in 0..64 {
+        assert_eq!(Position::from_index(index).to_index(), index);
+    }
+    assert_eq!(Position::from_an('a', 1).to_index(), 0);
+    assert_eq!(Position::from_an('h', 8).to_index(), 63);
+}
+
+#[test]
+fn ignores_positions_off_the_board() {
+    let mut board = Board::new_arranged();
+    let fen = board.to_fen();
+    let pawn = *board.get_piece(Position::from_an('e', 2)).unwrap();
+
+    for position in [Position::new(8, 3), Position::new(0, 200)] {
+        assert!(board.get_piece(position).is_none());
+        assert!(board.remove_piece(position).is_none());
+        board.add_piece(position, pawn);
+    }
+
+    assert_eq!(board.to_fen(), fen);
+    assert_bitboards_match(&board);
+}
+
+#[test]
+fn counts_repetitions() {
+    let mut board = Board::new_arranged();
+    let shuffle = [("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")];
+
+    // every round of knight moves brings the start position back once more
+    for round in 1..=4 {
+        assert_eq!(board.repetition_count(), round);
+        assert_eq!(board.is_threefold_repetition(), round >= 3);
+        assert!(!board.is_fivefold_repetition());
+
+        for (from, to) in shuffle {
+            board.make_move(normal(from, to)).unwrap();
+        }
+    }
+
+    assert!(board.is_fivefold_repetition());
+    let mov = normal("e2", "e4");
+    assert_eq!(board.make_move(mov), Err(MoveError::GameOver));
+
+    // positions in between only count for the side that was to move in them
+    let undo = board.make_move_no_checks(mov);
+    assert_eq!(board.repetition_count(), 1);
+    board.unmake_move(undo);
+    assert_eq!(board.repetition_count(), 5);
+}
+
+#[test]
+fn castling_rights_take_part_in_repetitions() {
+    let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+    for (from, to) in [("a1", "a2"), ("a8", "a7"), ("a2", "a1"), ("a7", "a8")] {
+        board.make_move(normal(from, to)).unwrap();
+    }
+
+    // the pieces are back, but neither side can castle queenside anymore
+    assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w Kk - 4 3");
+    assert_eq!(board.repetition_count(), 1);
+}
+
+#[test]
+fn keeps_the_clocks() {
+    let mut board = Board::new_arranged();
+    let moves = [
+        (("g1", "f3"), 1, 1),
+        (("g8", "f6"), 2, 2),
+        (("e2", "e4"), 0, 2),
+        (("f6", "e4"), 0, 3),
+        (("f1", "e2"), 1, 3),
+    ];
+
+    for ((from, to), halfmove_clock, fullmove_number) in moves {
+        board.make_move(normal(from, to)).unwrap();
+        assert_eq!(board.get_modifiers().halfmove_clock, halfmove_clock);
+        assert_eq!(board.get_modifiers().fullmove_number, fullmove_number);
+    }
+}
+
+#[test]
+fn applies_the_fifty_and_seventy_five_move_rules() {
+    let mut board = Board::from_fen("8/8/4k3/8/8/3K4/8/7R w - - 99 80").unwrap();
+    assert!(!board.can_claim_fifty_move_draw());
+    board.make_move(normal("h1", "h2")).unwrap();
+    assert!(board.can_claim_fifty_move_draw());
+    assert!(!board.is_seventy_five_move_draw());
+
+    let mut board = Board::from_fen("8/8/4k3/8/8/3K4/8/7R w - - 149 105").unwrap();
+    board.make_move(normal("h1", "h2")).unwrap();
+    assert!(board.is_seventy_five_move_draw());
+    assert_eq!(
+        board.make_move(normal("e6", "e5")),
+        Err(MoveError::GameOver)
+    );
+
+    // checkmate on the seventy-fifth move still wins the game
+    let mut board = Board::from_fen("k7/8/1K6/8/8/8/8/7R w - - 149 105").unwrap();
+    board.make_move(normal("h1", "h8")).unwrap();
+    assert!(!board.is_seventy_five_move_draw());
+}
+
+#[test]
+fn clocks_do_not_overflow() {
+    // the game is over by then, but the clock keeps counting
+    let mut board = Board::from_fen("8/8/4k3/8/8/3K4/8/7R w - - 300 200").unwrap();
+    board.make_move_no_checks(normal("h1", "h2"));
+    assert_eq!(board.get_modifiers().halfmove_clock, 301);
+}
+
+#[test]
+fn detects_insufficient_material() {
+    let insufficient = [
+        "8/8/4k3/8/8/3K4/8/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/6N1 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/6b1 w - - 0 1",
+        // bishops on dark squares only, however many there are
+        "8/8/4kb2/8/8/3K4/8/2B3B1 w - - 0 1",
+    ];
+    let sufficient = [
+        "8/8/4k3/8/8/3K4/8/1B4b1 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/1N4n1 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/2B3N1 w - - 0 1",
+        "8/8/4k3/8/8/3K4/4P3/8 w - - 0 1",
+        "8/8/4k3/8/8/3K4/8/7r w - - 0 1",
+    ];
+
+    for fen in insufficient {
+        assert!(
+            Board::from_fen(fen).unwrap().is_insufficient_material(),
+            "{}",
+            fen
+        );
+    }
+    for fen in sufficient {
+        assert!(
+            !Board::from_fen(fen).unwrap().is_insufficient_material(),
+            "{}",
+            fen
+        );
+    }
+
+    // capturing the last pawn ends the game
+    let mut board = Board::from_fen("8/8/8/4p3/3K4/8/8/4k3 w - - 0 1").unwrap();
+    board.make_move(normal("d4", "e5")).unwrap();
+    assert!(board.is_insufficient_material());
+    assert_eq!(
+        board.make_move(normal("e1", "e2")),
+        Err(MoveError::GameOver)
+    );
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use chess_base::board::{Board, MoveError};
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use common::{normal, square};
+
+/// Returns the castling moves the side to move can make in the position
+fn castles(fen: &str) -> Vec<Castles> {
+    let board = Board::from_fen(fen).unwrap();
+    let moves = match board.get_turn() {
+        Color::White => board.get_all_legal_moves_for_white(),
+        Color::Black => board.get_all_legal_moves_for_black(),
+    };
+
+    moves
+        .into_iter()
+        .filter_map(|mov| match mov {
+            Move::Castle { castle_type, .. } => Some(castle_type),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn castles_when_every_rule_is_met() {
+    assert_eq!(
+        castles("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+        vec![Castles::KingSide, Castles::QueenSide]
+    );
+    assert_eq!(
+        castles("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1"),
+        vec![Castles::KingSide, Castles::QueenSide]
+    );
+}
+
+#[test]
+fn does_not_castle_out_of_check() {
+    assert!(castles("r3k2r/8/8/8/4r3/8/8/R3K2R w KQkq - 0 1").is_empty());
+    assert!(castles("r3k2r/8/8/1B6/8/8/8/R3K2R b KQkq - 0 1").is_empty());
+}
+
+#[test]
+fn does_not_castle_through_or_into_check() {
+    // the king passes f1 or d1 and lands on g1 or c1
+    assert_eq!(
+        castles("r3kr2/8/8/8/8/8/8/R3K2R w KQq - 0 1"),
+        vec![Castles::QueenSide]
+    );
+    assert_eq!(
+        castles("r3k1r1/8/8/8/8/8/8/R3K2R w KQq - 0 1"),
+        vec![Castles::QueenSide]
+    );
+    assert_eq!(
+        castles("r2rk3/8/8/8/8/8/8/R3K2R w KQ - 0 1"),
+        vec![Castles::KingSide]
+    );
+    assert_eq!(
+        castles("r1r1k3/8/8/8/8/8/8/R3K2R w KQ - 0 1"),
+        vec![Castles::KingSide]
+    );
+
+    // only the rook crosses b1, so an attack on it does not matter
+    assert_eq!(
+        castles("1r2k3/8/8/8/8/8/8/R3K3 w Q - 0 1"),
+        vec![Castles::QueenSide]
+    );
+}
+
+#[test]
+fn needs_the_rook_on_its_starting_square() {
+    assert_eq!(
+        castles("4k3/8/8/8/8/8/8/4K2R w KQ - 0 1"),
+        vec![Castles::KingSide]
+    );
+    assert_eq!(
+        castles("4k3/8/8/8/8/8/8/N3K2B w KQ - 0 1"),
+        Vec::<Castles>::new()
+    );
+    assert_eq!(
+        castles("4k3/8/8/8/8/8/8/n3K2R w KQ - 0 1"),
+        vec![Castles::KingSide]
+    );
+}
+
+#[test]
+fn needs_every_square_between_king_and_rook_empty() {
+    assert_eq!(
+        castles("4k3/8/8/8/8/8/8/RN2K1NR w KQ - 0 1"),
+        Vec::<Castles>::new()
+    );
+    assert_eq!(
+        castles("4k3/8/8/8/8/8/8/R2QK2R w KQ - 0 1"),
+        vec![Castles::KingSide]
+    );
+}
+
+#[test]
+fn rejects_illegal_castling() {
+    let mut board = Board::from_fen("r3kr2/8/8/8/8/8/8/R3K2R w KQq - 0 1").unwrap();
+    let mov = Move::Castle {
+        color: Color::White,
+        castle_type: Castles::KingSide,
+    };
+    assert_eq!(
+        board.make_move(mov),
+        Err(MoveError::CastlingNotAllowed(Castles::KingSide))
+    );
+}
+
+#[test]
+fn keeps_checking_the_moves_after_an_illegal_castle() {
+    // castling kingside is illegal here, the moves listed after it must still be found
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    assert_eq!(board.get_all_legal_moves_for_white().len(), 48);
+    assert_eq!(board.perft(3), 97862);
+
+    let board = Board::from_fen("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")
+        .unwrap();
+    assert_eq!(board.perft(3), 9467);
+}
+
+/// Plays the move on the position and returns the castling field of the resulting FEN
+fn rights_after(fen: &str, mov: Move) -> String {
+    let mut board = Board::from_fen(fen).unwrap();
+    board.make_move(mov).unwrap();
+    board.to_fen().split(' ').nth(2).unwrap().to_string()
+}
+
+#[test]
+fn updates_the_rights() {
+    let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+
+    // moving the king or a rook
+    assert_eq!(rights_after(fen, normal("e1", "e2")), "kq");
+    assert_eq!(rights_after(fen, normal("a1", "a2")), "Kkq");
+    assert_eq!(rights_after(fen, normal("h1", "h2")), "Qkq");
+    let mov = Move::Castle {
+        color: Color::White,
+        castle_type: Castles::QueenSide,
+    };
+    assert_eq!(rights_after(fen, mov), "kq");
+
+    // capturing a rook on its starting square takes the opponent's right away
+    assert_eq!(rights_after(fen, normal("h1", "h8")), "Qq");
+    assert_eq!(rights_after(fen, normal("a1", "a8")), "Kk");
+
+    // also when a pawn captures it while promoting
+    let mov = Move::Promotion {
+        from: square("b7"),
+        to: square("a8"),
+        promotion: Pieces::Queen,
+    };
+    assert_eq!(rights_after("r3k2r/1P6/8/8/8/8/8/4K3 w kq - 0 1", mov), "k");
+
+    // other moves keep the rights
+    assert_eq!(rights_after(fen, normal("a1", "b1")), "Kkq");
+    assert_eq!(rights_after(fen, normal("h1", "g1")), "Qkq");
+}
@@ -0,0 +1,18 @@
+use chess_base::core::mov::Move;
+use chess_base::core::position::Position;
+
+/// Returns the position of a square written like "e4"
+pub fn square(name: &str) -> Position {
+    let mut chars = name.chars();
+    let file = chars.next().unwrap();
+    let rank = chars.next().unwrap().to_digit(10).unwrap() as u8;
+    Position::from_an(file, rank)
+}
+
+/// Returns the normal move between two squares written like "e2" and "e4"
+pub fn normal(from: &str, to: &str) -> Move {
+    Move::Normal {
+        from: square(from),
+        to: square(to),
+    }
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use chess_base::board::Board;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use common::{normal, square};
+
+fn legal_moves(board: &Board) -> Vec<Move> {
+    match board.get_turn() {
+        Color::White => board.get_all_legal_moves_for_white(),
+        Color::Black => board.get_all_legal_moves_for_black(),
+    }
+}
+
+fn en_passant_moves(fen: &str) -> Vec<Move> {
+    let board = Board::from_fen(fen).unwrap();
+    legal_moves(&board)
+        .into_iter()
+        .filter(|mov| matches!(mov, Move::EnPassant { .. }))
+        .collect()
+}
+
+#[test]
+fn removes_the_captured_pawn() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let mov = Move::EnPassant {
+        from: square("e5"),
+        to: square("f6"),
+    };
+    assert_eq!(en_passant_moves(&board.to_fen()), vec![mov]);
+
+    let undo = board.make_move_no_checks(mov);
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/ppp1p1pp/5P2/3p4/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+    );
+
+    board.unmake_move(undo);
+    assert_eq!(
+        board.to_fen(),
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3"
+    );
+}
+
+#[test]
+fn accepts_en_passant_as_a_normal_move() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+    let outcome = board.make_move(normal("e5", "f6")).unwrap();
+
+    assert!(outcome.is_en_passant);
+    assert!(board.get_piece(square("f5")).is_none());
+}
+
+#[test]
+fn does_not_uncover_the_king_along_the_rank() {
+    // both pawns leave the fifth rank, which would open it for the rook
+    assert!(en_passant_moves("8/8/8/KPp4r/8/8/8/4k3 w - c6 0 1").is_empty());
+    let board = Board::from_fen("8/8/8/KPp4r/8/8/8/4k3 w - c6 0 1").unwrap();
+    assert_eq!(board.perft(1), 4);
+
+    // and diagonally, through the square of the captured pawn
+    assert_eq!(
+        en_passant_moves("7k/8/8/8/3Pp3/8/8/4K3 b - d3 0 1").len(),
+        1
+    );
+    assert!(en_passant_moves("7k/8/8/8/3Pp3/8/8/B3K3 b - d3 0 1").is_empty());
+}
+
+#[test]
+fn captures_a_checking_pawn_en_passant() {
+    // the pawn that just moved gives check and capturing it is the way out
+    let moves = en_passant_moves("8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1");
+    assert_eq!(
+        moves,
+        vec![Move::EnPassant {
+            from: square("e4"),
+            to: square("d3"),
+        }]
+    );
+}
+
+#[test]
+fn only_the_side_to_move_captures_en_passant() {
+    // the black pawn on f7 could reach e6 diagonally, but the square is for white
+    assert_eq!(
+        en_passant_moves("4k3/5p2/8/3Pp3/8/8/8/4K3 w - e6 0 1"),
+        vec![Move::EnPassant {
+            from: square("d5"),
+            to: square("e6"),
+        }]
+    );
+    let board = Board::from_fen("4k3/5p2/8/3Pp3/8/8/8/4K3 w - e6 0 1").unwrap();
+    assert!(!board
+        .get_all_legal_moves()
+        .iter()
+        .any(|mov| matches!(mov, Move::EnPassant { from, .. } if *from == square("f7"))));
+}
+
+#[test]
+fn needs_a_pawn_of_the_other_side_to_take() {
+    let fen = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+    let mov = Move::EnPassant {
+        from: square("e5"),
+        to: square("f6"),
+    };
+
+    // the pawn that moved two squares is gone, the en passant square is left behind
+    let mut board = Board::from_fen(fen).unwrap();
+    board.remove_piece(square("f5"));
+    assert!(!legal_moves(&board).contains(&mov));
+
+    // a white pawn next to the white pawn can not be taken either
+    let mut board = Board::from_fen(fen).unwrap();
+    let pawn = board.remove_piece(square("f5")).unwrap();
+    let white_pawn = *board.get_piece(square("e5")).unwrap();
+    board.add_piece(square("f5"), white_pawn);
+    assert!(!legal_moves(&board).contains(&mov));
+
+    // played anyway, the move takes nothing that is not an enemy pawn
+    let undo = board.make_move_no_checks(mov);
+    assert_eq!(undo.get_captured(), None);
+    assert!(board.get_piece(square("f5")).is_some());
+    board.unmake_move(undo);
+
+    board.remove_piece(square("f5"));
+    board.add_piece(square("f5"), pawn);
+    assert!(legal_moves(&board).contains(&mov));
+}
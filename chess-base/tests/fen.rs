@@ -0,0 +1,189 @@
+use chess_base::board::{Board, FenErrorKind, FenField};
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+
+#[test]
+fn parses_all_fields() {
+    let board =
+        Board::from_fen("rnbqkbnr/pp1ppppp/8/2p5/4P3/5N2/PPPP1PPP/RNBQKB1R w Kq c6 1 2").unwrap();
+
+    assert_eq!(board.get_turn(), Color::White);
+
+    let modifiers = board.get_modifiers();
+    let rights = modifiers.castling_rights;
+    assert!(rights.has(Color::White, Castles::KingSide));
+    assert!(!rights.has(Color::White, Castles::QueenSide));
+    assert!(!rights.has(Color::Black, Castles::KingSide));
+    assert!(rights.has(Color::Black, Castles::QueenSide));
+    assert_eq!(modifiers.en_passant, Some(Position::from_an('c', 6)));
+    assert_eq!(modifiers.halfmove_clock, 1);
+    assert_eq!(modifiers.fullmove_number, 2);
+
+    let knight = board.get_piece(Position::from_an('f', 3)).unwrap();
+    assert_eq!(knight.get_piece_type(), Pieces::Knight);
+    assert_eq!(knight.get_color(), Color::White);
+    assert!(board.get_piece(Position::from_an('g', 1)).is_none());
+}
+
+#[test]
+fn clocks_are_optional() {
+    let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+
+    assert_eq!(board.get_modifiers().halfmove_clock, 0);
+    assert_eq!(board.get_modifiers().fullmove_number, 1);
+}
+
+#[test]
+fn reports_field_and_offset() {
+    let cases = [
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1",
+            FenField::PiecePlacement,
+            42,
+            FenErrorKind::UnexpectedCharacter('X'),
+        ),
+        (
+            "rnbqkbnr/pppppppp/8p/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            FenField::PiecePlacement,
+            19,
+            FenErrorKind::RankTooLong,
+        ),
+        (
+            "rnbqkbnr/pppppppp/7/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            FenField::PiecePlacement,
+            19,
+            FenErrorKind::RankTooShort,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            FenField::PiecePlacement,
+            41,
+            FenErrorKind::WrongRankCount,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQQBNR w KQkq - 0 1",
+            FenField::PiecePlacement,
+            0,
+            FenErrorKind::InvalidKingCount(Color::White),
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+            FenField::SideToMove,
+            44,
+            FenErrorKind::UnexpectedCharacter('x'),
+        ),
+        // the black king could be taken
+        (
+            "4k3/4R3/8/8/8/8/8/4K3 w - - 0 1",
+            FenField::SideToMove,
+            22,
+            FenErrorKind::OpponentInCheck,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkK - 0 1",
+            FenField::Castling,
+            49,
+            FenErrorKind::DuplicateCastlingRight('K'),
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1",
+            FenField::EnPassant,
+            52,
+            FenErrorKind::InvalidEnPassantSquare,
+        ),
+        // no pawn in front of the square
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq e3 0 1",
+            FenField::EnPassant,
+            51,
+            FenErrorKind::InvalidEnPassantSquare,
+        ),
+        // the pawn in front of the square is of the side to move
+        (
+            "rnbqkbnr/pppp1ppp/8/8/4p3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            FenField::EnPassant,
+            53,
+            FenErrorKind::InvalidEnPassantSquare,
+        ),
+        // a piece stands on the square
+        (
+            "rnbqkbnr/pppppppp/8/8/4P3/4N3/PPPP1PPP/RNBQKB1R b KQkq e3 0 1",
+            FenField::EnPassant,
+            55,
+            FenErrorKind::InvalidEnPassantSquare,
+        ),
+        // the pawn could not have come from its starting square
+        (
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPPPPPP/RNBQKBNR b KQkq e3 0 1",
+            FenField::EnPassant,
+            53,
+            FenErrorKind::InvalidEnPassantSquare,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1",
+            FenField::HalfmoveClock,
+            53,
+            FenErrorKind::UnexpectedCharacter('x'),
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 0",
+            FenField::FullmoveNumber,
+            55,
+            FenErrorKind::InvalidNumber,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w",
+            FenField::Castling,
+            45,
+            FenErrorKind::MissingField,
+        ),
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 extra",
+            FenField::FullmoveNumber,
+            57,
+            FenErrorKind::TrailingInput,
+        ),
+    ];
+
+    for (fen, field, offset, kind) in cases {
+        let error = Board::from_fen(fen).unwrap_err();
+        assert_eq!(error.field, field, "{}", fen);
+        assert_eq!(error.offset, offset, "{}", fen);
+        assert_eq!(error.kind, kind, "{}", fen);
+    }
+}
+
+/// Positions that exercise every FEN field: partial castling rights, en passant squares
+/// for both sides, promotions about to happen, empty ranks and large clocks
+const ROUND_TRIP: [&str; 12] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+    "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+    "4k3/8/8/8/8/8/8/4K3 b - - 99 150",
+    "1k6/1P6/8/8/8/8/6p1/6K1 b - - 0 60",
+    "r3k3/8/8/8/8/8/8/4K2R w Kq - 12 34",
+    "8/8/8/3pP3/8/8/8/K6k w - d6 0 2",
+];
+
+#[test]
+fn round_trips_corpus() {
+    for fen in ROUND_TRIP {
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(board.to_fen(), fen);
+    }
+}
+
+#[test]
+fn starting_position_matches_standard() {
+    assert_eq!(
+        Board::new_arranged().to_fen(),
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+    );
+}
@@ -0,0 +1,169 @@
+use chess_base::board::{Board, GameStatus};
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::game::{Game, NodeId};
+use chess_base::pgn::{GameResult, PgnGame};
+use std::time::Duration;
+
+/// Plays the moves in standard algebraic notation at the cursor
+fn play(game: &mut Game, sans: &[&str]) {
+    for san in sans {
+        let mov = Move::from_san(san, game.get_board()).unwrap();
+        game.make_move(mov).unwrap();
+    }
+}
+
+fn sans(game: &Game, line: &[NodeId]) -> Vec<String> {
+    line.iter()
+        .map(|&id| game.node(id).get_san().to_string())
+        .collect()
+}
+
+#[test]
+fn keeps_the_moves_with_their_san() {
+    let mut game = Game::new();
+    play(&mut game, &["e4", "e5", "Nf3", "Nc6", "Bb5"]);
+
+    assert_eq!(game.ply(), 5);
+    assert_eq!(
+        sans(&game, &game.main_line()),
+        ["e4", "e5", "Nf3", "Nc6", "Bb5"]
+    );
+    assert_eq!(
+        game.get_board().to_fen(),
+        "r1bqkbnr/pppp1ppp/2n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R b KQkq - 3 3"
+    );
+
+    // illegal moves are rejected and leave the game as it was
+    let illegal = Move::from_san("e4", &Board::new_arranged()).unwrap();
+    assert!(game.make_move(illegal).is_err());
+    assert_eq!(game.ply(), 5);
+}
+
+#[test]
+fn undo_and_redo_walk_the_line() {
+    let mut game = Game::new();
+    play(&mut game, &["d4", "d5", "c4"]);
+    let end = game.get_board().to_fen();
+
+    assert!(game.undo());
+    assert!(game.undo());
+    assert_eq!(game.ply(), 1);
+    assert_eq!(
+        game.get_board().to_fen(),
+        "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 1"
+    );
+
+    assert!(game.redo());
+    assert!(game.redo());
+    assert!(!game.redo());
+    assert_eq!(game.get_board().to_fen(), end);
+
+    assert!(game.goto(0));
+    assert_eq!(game.get_board().to_fen(), Board::new_arranged().to_fen());
+    assert!(!game.undo());
+    assert!(!game.goto(4));
+    assert!(game.goto(3));
+    assert_eq!(game.get_board().to_fen(), end);
+    assert_eq!(game.main_line().len(), 3);
+}
+
+#[test]
+fn a_different_move_starts_a_variation() {
+    let mut game = Game::new();
+    play(&mut game, &["e4", "e5", "Nf3"]);
+    game.goto(2);
+    play(&mut game, &["Bc4", "Nf6"]);
+
+    // the main line stays, the new moves are the current line
+    assert_eq!(sans(&game, &game.main_line()), ["e4", "e5", "Nf3"]);
+    assert_eq!(sans(&game, game.current_line()), ["e4", "e5", "Bc4", "Nf6"]);
+    let after_e5 = game.main_line()[1];
+    assert_eq!(game.node(after_e5).get_children().len(), 2);
+
+    // playing a move that is already there does not add it twice
+    game.goto(2);
+    play(&mut game, &["Nf3"]);
+    assert_eq!(game.node(after_e5).get_children().len(), 2);
+    assert_eq!(sans(&game, game.current_line()), ["e4", "e5", "Nf3"]);
+
+    // the board can be derived at any node, and the cursor can jump to it
+    let nf6 = game.node(after_e5).get_children()[1];
+    let nf6 = game.node(nf6).get_children()[0];
+    assert_eq!(
+        game.board_at(nf6).to_fen(),
+        "rnbqkb1r/pppp1ppp/5n2/4p3/2B1P3/8/PPPP1PPP/RNBQK1NR w KQkq - 2 3"
+    );
+    game.goto_node(nf6);
+    assert_eq!(game.cursor(), nf6);
+    assert_eq!(game.get_board().to_fen(), game.board_at(nf6).to_fen());
+    assert!(game.undo());
+    assert!(game.undo());
+    assert!(game.redo());
+    assert_eq!(game.node(game.cursor()).get_san(), "Bc4");
+
+    game.promote_to_main_line(nf6);
+    assert_eq!(sans(&game, &game.main_line()), ["e4", "e5", "Bc4", "Nf6"]);
+    assert_eq!(
+        game.board_at(game.root()).to_fen(),
+        Board::new_arranged().to_fen()
+    );
+}
+
+#[test]
+fn resignation_is_taken_back_with_the_move() {
+    let mut game = Game::new();
+    play(&mut game, &["f3", "e5", "g4", "Qh4#"]);
+    assert_eq!(
+        game.status(),
+        GameStatus::Checkmate {
+            winner: Color::Black
+        }
+    );
+
+    game.undo();
+    game.resign(Color::White);
+    assert!(game.status().is_over());
+    game.undo();
+    assert_eq!(game.status(), GameStatus::Ongoing);
+}
+
+#[test]
+fn converts_to_and_from_pgn() {
+    let mut game = Game::new();
+    play(&mut game, &["e4", "c5", "Nf3"]);
+    game.goto(1);
+    play(&mut game, &["e5"]);
+    game.node_mut(game.cursor()).comment = Some("Open game".to_string());
+    game.node_mut(game.cursor()).time_spent = Some(Duration::from_secs(12));
+
+    let pgn = game.to_pgn();
+    assert_eq!(pgn.result, GameResult::Unknown);
+    assert_eq!(pgn.moves.len(), 3);
+    assert_eq!(pgn.moves[1].san, "c5");
+    assert_eq!(pgn.moves[1].variations[0][0].san, "e5");
+    assert!(pgn
+        .to_pgn()
+        .contains("1. e4 c5 (1... e5 {[%emt 0:00:12]} {Open game}) 2. Nf3 *"));
+
+    let read = Game::from_pgn(&PgnGame::from_pgn(&pgn.to_pgn()).unwrap()).unwrap();
+    assert_eq!(read.ply(), 3);
+    assert_eq!(
+        read.get_board().to_fen(),
+        game.board_at(game.main_line()[2]).to_fen()
+    );
+    let e5 = read.node(read.main_line()[0]).get_children()[1];
+    assert_eq!(read.node(e5).comment.as_deref(), Some("Open game"));
+    assert_eq!(read.node(e5).time_spent, Some(Duration::from_secs(12)));
+
+    // a game from another position keeps it
+    let board = Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    let mut game = Game::from_board(board);
+    play(&mut game, &["e4"]);
+    let pgn = game.to_pgn();
+    assert_eq!(pgn.tag("FEN"), Some("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"));
+    assert_eq!(
+        Game::from_pgn(&pgn).unwrap().get_board().to_fen(),
+        game.get_board().to_fen()
+    );
+}
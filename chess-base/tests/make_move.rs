@@ -0,0 +1,259 @@
+mod common;
+
+use chess_base::board::{Board, DrawReason, GameStatus, MoveError};
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::piece::Piece;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use common::{normal, square};
+
+#[test]
+fn reports_why_a_move_is_rejected() {
+    let mut board = Board::new_arranged();
+
+    assert_eq!(
+        board.make_move(normal("e3", "e4")),
+        Err(MoveError::NoPiece(square("e3")))
+    );
+    assert_eq!(
+        board.make_move(normal("e7", "e5")),
+        Err(MoveError::OpponentPiece(square("e7")))
+    );
+    assert_eq!(
+        board.make_move(normal("e2", "e5")),
+        Err(MoveError::InvalidMove(square("e2"), square("e5")))
+    );
+    assert_eq!(
+        board.make_move(Move::Normal {
+            from: square("e2"),
+            to: Position::new(4, 8),
+        }),
+        Err(MoveError::OffBoard(Position::new(4, 8)))
+    );
+    assert_eq!(
+        board.make_move(Move::Castle {
+            color: Color::Black,
+            castle_type: Castles::KingSide,
+        }),
+        Err(MoveError::NotYourTurn(Color::Black))
+    );
+    assert_eq!(
+        board.make_move(Move::Castle {
+            color: Color::White,
+            castle_type: Castles::KingSide,
+        }),
+        Err(MoveError::CastlingNotAllowed(Castles::KingSide))
+    );
+    assert_eq!(
+        board.make_move(Move::Promotion {
+            from: square("e2"),
+            to: square("e4"),
+            promotion: Pieces::Queen,
+        }),
+        Err(MoveError::NotAPromotion)
+    );
+
+    // a rejected move leaves the board untouched
+    assert_eq!(board.to_fen(), Board::new_arranged().to_fen());
+}
+
+#[test]
+fn rejects_moves_that_leave_the_king_in_check() {
+    let mut board = Board::from_fen("4k3/8/8/8/8/8/4r3/4K2R w K - 0 1").unwrap();
+
+    assert_eq!(
+        board.make_move(normal("h1", "h2")),
+        Err(MoveError::LeavesKingInCheck)
+    );
+}
+
+#[test]
+fn requires_a_valid_promotion() {
+    let mut board = Board::from_fen("7k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+
+    assert_eq!(
+        board.make_move(normal("e7", "e8")),
+        Err(MoveError::MissingPromotion)
+    );
+    assert_eq!(
+        board.make_move(Move::Promotion {
+            from: square("e7"),
+            to: square("e8"),
+            promotion: Pieces::King,
+        }),
+        Err(MoveError::InvalidPromotionPiece(Pieces::King))
+    );
+
+    let outcome = board
+        .make_move(Move::Promotion {
+            from: square("e7"),
+            to: square("e8"),
+            promotion: Pieces::Queen,
+        })
+        .unwrap();
+    assert!(outcome.is_check);
+    assert_eq!(
+        board.get_piece(square("e8")).unwrap().get_piece_type(),
+        Pieces::Queen
+    );
+}
+
+#[test]
+fn reports_captures_and_en_passant() {
+    let mut board =
+        Board::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3").unwrap();
+
+    let outcome = board.make_move(normal("e5", "f6")).unwrap();
+    assert_eq!(outcome.captured, Some(Pieces::Pawn));
+    assert!(outcome.is_en_passant);
+    assert!(!outcome.is_check);
+    assert!(board.get_piece(square("f5")).is_none());
+
+    let outcome = board.make_move(normal("g8", "f6")).unwrap();
+    assert_eq!(outcome.captured, Some(Pieces::Pawn));
+    assert!(!outcome.is_en_passant);
+}
+
+#[test]
+fn reports_checkmate() {
+    let mut board = Board::new_arranged();
+
+    for (from, to) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4")] {
+        let outcome = board.make_move(normal(from, to)).unwrap();
+        assert!(!outcome.is_check);
+    }
+
+    let outcome = board.make_move(normal("d8", "h4")).unwrap();
+    assert!(outcome.is_check);
+    assert!(outcome.is_checkmate);
+    assert_eq!(outcome.captured, None);
+}
+
+#[test]
+fn unmake_restores_the_position() {
+    // castling, promotions with and without capture, and en passant are all available here
+    let fens = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1",
+        "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    ];
+
+    for fen in fens {
+        let mut board = Board::from_fen(fen).unwrap();
+        let moves = match board.get_turn() {
+            Color::White => board.get_all_legal_moves_for_white(),
+            Color::Black => board.get_all_legal_moves_for_black(),
+        };
+        assert!(!moves.is_empty());
+
+        for mov in moves {
+            let undo = board.make_move_no_checks(mov);
+            assert_eq!(undo.get_move(), mov);
+            board.unmake_move(undo);
+            assert_eq!(board.to_fen(), fen, "after {:?}", mov);
+        }
+    }
+}
+
+#[test]
+fn legal_moves_are_checked_against_the_king_of_the_moving_color() {
+    // the white bishop is pinned, even though it is black to move
+    let board = Board::from_fen("4r1k1/8/8/8/8/8/4B3/4K3 b - - 0 1").unwrap();
+    let moves = board.get_all_legal_moves_for(Color::White);
+    assert_eq!(moves.len(), 4, "{:?}", moves);
+    assert!(moves
+        .iter()
+        .all(|mov| !matches!(mov, Move::Normal { from, .. } if *from == square("e2"))));
+    assert_eq!(board.get_all_legal_moves_for_white(), moves);
+
+    // white is mated on the back rank, whoever is to move,
+    // a FEN can not say so, the rook is put there afterwards
+    let mut board = Board::from_fen("6k1/8/8/8/8/8/5PPP/6K1 b - - 0 1").unwrap();
+    let rook = Piece::new(square("a1"), Color::Black, Pieces::Rook);
+    board.add_piece(square("a1"), rook);
+    assert!(board.in_checkmate(Color::White));
+    assert!(!board.in_stalemate(Color::White));
+    assert!(!board.in_checkmate(Color::Black));
+
+    let board = Board::from_fen("k7/2Q5/1K6/8/8/8/8/8 w - - 0 1").unwrap();
+    assert!(board.in_stalemate(Color::Black));
+    assert!(!board.in_stalemate(Color::White));
+}
+
+#[test]
+fn the_moving_piece_keeps_its_color() {
+    // a white pawn promotes while it is black to move
+    let fen = "4k3/P7/8/8/8/8/8/4K3 b - - 0 1";
+    let mut board = Board::from_fen(fen).unwrap();
+    let undo = board.make_move_no_checks(Move::Promotion {
+        from: square("a7"),
+        to: square("a8"),
+        promotion: Pieces::Queen,
+    });
+    let queen = board.get_piece(square("a8")).unwrap();
+    assert_eq!(queen.get_color(), Color::White);
+    assert_eq!(queen.get_piece_type(), Pieces::Queen);
+
+    board.unmake_move(undo);
+    assert_eq!(board.to_fen(), fen);
+}
+
+#[test]
+fn reports_the_game_status() {
+    let status = |fen| Board::from_fen(fen).unwrap().status();
+
+    assert_eq!(Board::new_arranged().status(), GameStatus::Ongoing);
+    assert_eq!(
+        status("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"),
+        GameStatus::Checkmate {
+            winner: Color::Black
+        }
+    );
+    assert_eq!(
+        status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"),
+        GameStatus::Stalemate
+    );
+    assert_eq!(
+        status("8/8/4k3/8/8/3K4/8/6N1 w - - 0 1"),
+        GameStatus::Draw(DrawReason::InsufficientMaterial)
+    );
+    assert_eq!(
+        status("8/8/4k3/8/8/3K4/8/7R w - - 150 120"),
+        GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+    );
+
+    // a checkmated side is not stalemated
+    let board =
+        Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3").unwrap();
+    assert!(board.in_checkmate(Color::White));
+    assert!(!board.in_stalemate(Color::White));
+}
+
+#[test]
+fn ends_the_game_by_resignation_or_claim() {
+    let mut board = Board::new_arranged();
+    board.resign(Color::White);
+    assert_eq!(
+        board.status(),
+        GameStatus::Resigned {
+            winner: Color::Black
+        }
+    );
+    assert_eq!(
+        board.make_move(normal("e2", "e4")),
+        Err(MoveError::GameOver)
+    );
+
+    let mut board = Board::from_fen("8/8/4k3/8/8/3K4/8/7R w - - 99 80").unwrap();
+    assert_eq!(board.claim_draw(), None);
+    board.make_move(normal("h1", "h2")).unwrap();
+    assert_eq!(board.claim_draw(), Some(DrawReason::FiftyMoveRule));
+    assert_eq!(board.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+
+    // once the game is over the result stays
+    board.resign(Color::Black);
+    assert_eq!(board.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+}
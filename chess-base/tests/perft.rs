@@ -0,0 +1,121 @@
+use chess_base::board::Board;
+
+// the positions and node counts from https://www.chessprogramming.org/Perft_Results
+const INITIAL: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const KIWIPETE: &str = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+const POSITION_3: &str = "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1";
+const POSITION_4: &str = "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1";
+const POSITION_4_MIRRORED: &str =
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1";
+const POSITION_5: &str = "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8";
+const POSITION_6: &str = "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10";
+
+/// Checks the node counts of a position, starting at depth 1
+fn assert_perft(fen: &str, nodes: &[u64]) {
+    let board = Board::from_fen(fen).unwrap();
+    for (depth, nodes) in nodes.iter().enumerate() {
+        let depth = depth as u32 + 1;
+        assert_eq!(board.perft(depth), *nodes, "{} at depth {}", fen, depth);
+    }
+}
+
+#[test]
+fn initial_position() {
+    assert_perft(INITIAL, &[20, 400, 8902, 197281]);
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467]);
+    assert_perft(POSITION_4_MIRRORED, &[6, 264, 9467]);
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
+
+#[test]
+fn position_6() {
+    assert_perft(POSITION_6, &[46, 2079, 89890]);
+}
+
+#[test]
+fn en_passant_regressions() {
+    // capturing en passant to get out of check, and a king next to pinned pawns
+    assert_perft(
+        "8/8/8/5k2/3p4/8/4P3/4K3 w - - 0 1",
+        &[6, 54, 343, 2810, 19228],
+    );
+    assert_perft(
+        "8/8/8/B2p3Q/2qPp1P1/b7/2P2PkP/4K2R b K - 0 1",
+        &[26, 611, 14583],
+    );
+}
+
+// the deeper counts take a while in debug builds, run them with
+// cargo test --release -- --ignored
+
+#[test]
+#[ignore]
+fn initial_position_deep() {
+    assert_perft(INITIAL, &[20, 400, 8902, 197281, 4865609]);
+}
+
+#[test]
+#[ignore]
+fn kiwipete_deep() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862, 4085603]);
+}
+
+#[test]
+#[ignore]
+fn position_3_deep() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624, 11030083]);
+}
+
+#[test]
+#[ignore]
+fn position_4_deep() {
+    assert_perft(POSITION_4, &[6, 264, 9467, 422333, 15833292]);
+}
+
+#[test]
+#[ignore]
+fn position_5_deep() {
+    assert_perft(POSITION_5, &[44, 1486, 62379, 2103487]);
+}
+
+#[test]
+#[ignore]
+fn position_6_deep() {
+    assert_perft(POSITION_6, &[46, 2079, 89890, 3894594]);
+}
+
+#[test]
+fn divide_adds_up_to_perft() {
+    for fen in [INITIAL, KIWIPETE, POSITION_4] {
+        let board = Board::from_fen(fen).unwrap();
+        let divide = board.perft_divide(2);
+
+        assert_eq!(divide.len() as u64, board.perft(1));
+        assert_eq!(
+            divide.iter().map(|(_, nodes)| nodes).sum::<u64>(),
+            board.perft(2)
+        );
+    }
+
+    // every first move in the initial position leaves black with twenty replies
+    let board = Board::new_arranged();
+    assert!(board.perft_divide(2).iter().all(|(_, nodes)| *nodes == 20));
+}
@@ -0,0 +1,274 @@
+use chess_base::board::Board;
+use chess_base::core::mov::Move;
+use chess_base::core::san::SanError;
+use chess_base::pgn::{read_pgn, write_pgn, GameResult, PgnErrorKind, PgnGame, PgnMove};
+use std::time::Duration;
+
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4?! {This is a weak move already.} 4. dxe5 Bxf3
+5. Qxf3 dxe5 6. Bc4 Nf6 7. Qb3 Qe7 8. Nc3 c6 9. Bg5 b5?! (9... Qb4+ 10. Qxb4
+Bxb4) 10. Nxb5! cxb5 11. Bxb5+ Nbd7 12. O-O-O Rd8 13. Rxd7 Rxd7 14. Rd1 Qe6
+15. Bxd7+ Nxd7 (15... Qxd7 16. Qb8+ Qd8 17. Qxd8#) 16. Qb8+ $3 Nxb8 17. Rd8# 1-0
+"#;
+
+#[test]
+fn reads_tags_moves_and_result() {
+    let game = PgnGame::from_pgn(OPERA_GAME).unwrap();
+
+    assert_eq!(game.tags.len(), 7);
+    assert_eq!(game.tags[0], ("Event".to_string(), "Paris".to_string()));
+    assert_eq!(game.tag("White"), Some("Paul Morphy"));
+    assert_eq!(game.tag("Black"), Some("Duke Karl / Count Isouard"));
+    assert_eq!(game.tag("ECO"), None);
+    assert_eq!(game.result, GameResult::WhiteWins);
+
+    assert_eq!(game.moves.len(), 33);
+    assert_eq!(game.moves[0].san, "e4");
+    assert_eq!(game.moves[32].san, "Rd8#");
+    assert_eq!(
+        game.replay().unwrap().to_fen(),
+        "1n1Rkb1r/p4ppp/4q3/4p1B1/4P3/8/PPP2PPP/2K5 b k - 1 17"
+    );
+}
+
+#[test]
+fn reads_comments_annotations_and_variations() {
+    let game = PgnGame::from_pgn(OPERA_GAME).unwrap();
+
+    let bg4 = &game.moves[5];
+    assert_eq!(bg4.san, "Bg4");
+    assert_eq!(bg4.nags, vec![6]);
+    assert_eq!(bg4.comments, vec!["This is a weak move already."]);
+
+    let b5 = &game.moves[17];
+    assert_eq!(b5.san, "b5");
+    assert_eq!(b5.variations.len(), 1);
+    let sans: Vec<&str> = b5.variations[0].iter().map(|m| m.san.as_str()).collect();
+    assert_eq!(sans, ["Qb4+", "Qxb4", "Bxb4"]);
+
+    assert_eq!(game.moves[18].nags, vec![1]);
+    assert_eq!(game.moves[30].nags, vec![3]);
+}
+
+#[test]
+fn keeps_clock_comments_it_can_not_read() {
+    let game = PgnGame::from_pgn("1. e4 {[%clk 1:02:03.5]} e5 {[%emt 0:00:07]} *").unwrap();
+    assert_eq!(game.moves[0].clock, Some(Duration::from_secs_f64(3723.5)));
+    assert!(game.moves[0].comments.is_empty());
+    assert_eq!(game.moves[1].time_spent, Some(Duration::from_secs(7)));
+
+    let broken = [
+        "[%clk 0:00:inf]",
+        "[%clk 0:00:1e30]",
+        "[%clk 0:00:NaN]",
+        "[%clk 0:00:-1]",
+        "[%clk 18446744073709551615:00:00]",
+        "[%emt 0:307445734561825861:00]",
+    ];
+    for comment in broken {
+        let game = PgnGame::from_pgn(&format!("1. e4 {{{}}} *", comment)).unwrap();
+        assert_eq!(game.moves[0].clock, None, "{}", comment);
+        assert_eq!(game.moves[0].time_spent, None, "{}", comment);
+        assert_eq!(game.moves[0].comments, vec![comment]);
+    }
+}
+
+#[test]
+fn reads_nested_variations_and_comments_before_moves() {
+    let pgn = "{Opening} 1. e4 (1. d4 d5 (1... Nf6 2. c4) 2. c4 {Queen's gambit}) \
+               ({Or} 1. c4) 1... c5 ; Sicilian\n *";
+    let game = PgnGame::from_pgn(pgn).unwrap();
+
+    assert_eq!(game.result, GameResult::Unknown);
+    assert_eq!(game.moves.len(), 2);
+    assert_eq!(game.moves[0].comments_before, vec!["Opening"]);
+    assert_eq!(game.moves[1].comments, vec!["Sicilian"]);
+
+    let variations = &game.moves[0].variations;
+    assert_eq!(variations.len(), 2);
+    assert_eq!(variations[0].len(), 3);
+    assert_eq!(variations[0][1].variations[0][1].san, "c4");
+    assert_eq!(variations[0][2].comments, vec!["Queen's gambit"]);
+    assert_eq!(variations[1][0].comments_before, vec!["Or"]);
+}
+
+#[test]
+fn reads_every_game_of_a_file() {
+    let pgn = format!(
+        "{}\n[Event \"Endgame\"]\n[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]\n\n\
+         1. e4 Kd7 2. e5 1/2-1/2\n\n[Event \"Unfinished\"]\n\n1. d4\n\n\
+         [Event \"No moves\"]\n",
+        OPERA_GAME
+    );
+    let games = read_pgn(&pgn).unwrap();
+
+    assert_eq!(games.len(), 4);
+    assert_eq!(games[1].result, GameResult::Draw);
+    assert_eq!(
+        games[1].replay().unwrap().to_fen(),
+        "8/3k4/8/4P3/8/8/8/4K3 b - - 0 2"
+    );
+    assert_eq!(games[2].tag("Event"), Some("Unfinished"));
+    assert_eq!(games[2].moves.len(), 1);
+    assert_eq!(games[2].result, GameResult::Unknown);
+    assert!(games[3].moves.is_empty());
+
+    assert!(read_pgn("  \n").unwrap().is_empty());
+    assert_eq!(
+        PgnGame::from_pgn("").unwrap_err().kind,
+        PgnErrorKind::NoGame
+    );
+}
+
+#[test]
+fn reports_line_and_column() {
+    let cases = [
+        (
+            "1. e4 e5\n2. Ke3 *",
+            2,
+            4,
+            PgnErrorKind::InvalidMove("Ke3".to_string(), SanError::IllegalMove),
+        ),
+        (
+            "1. e4 e5 2. Nf3 {unfinished\ncomment",
+            1,
+            17,
+            PgnErrorKind::UnterminatedComment,
+        ),
+        (
+            "[Event \"Test]\n1. e4 *",
+            1,
+            8,
+            PgnErrorKind::UnterminatedString,
+        ),
+        ("[Event]\n1. e4 *", 1, 1, PgnErrorKind::InvalidTag),
+        (
+            "1. e4\n  (1. d4 d5\n2. c4",
+            2,
+            3,
+            PgnErrorKind::UnterminatedVariation,
+        ),
+        (
+            "1. e4 e5 ) *",
+            1,
+            10,
+            PgnErrorKind::UnexpectedToken(")".to_string()),
+        ),
+        ("1. e4 & e5 *", 1, 7, PgnErrorKind::UnexpectedCharacter('&')),
+        ("1. e4 $256 *", 1, 7, PgnErrorKind::InvalidNag),
+        (
+            "1. e4 (1... e5) *",
+            1,
+            13,
+            PgnErrorKind::InvalidMove("e5".to_string(), SanError::IllegalMove),
+        ),
+    ];
+
+    for (pgn, line, column, kind) in cases {
+        let error = read_pgn(pgn).unwrap_err();
+        assert_eq!((error.line, error.column), (line, column), "{}", pgn);
+        assert_eq!(error.kind, kind, "{}", pgn);
+    }
+
+    let error = read_pgn("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]\n*").unwrap_err();
+    assert_eq!((error.line, error.column), (1, 1));
+    assert!(matches!(error.kind, PgnErrorKind::InvalidFen(_)));
+}
+
+/// Plays the moves from the position and returns them as a line of a game record
+fn line(board: &Board, sans: &[&str]) -> Vec<PgnMove> {
+    let mut board = board.clone();
+    sans.iter()
+        .map(|san| {
+            let mov = Move::from_san(san, &board).unwrap();
+            let pgn_move = PgnMove::new(mov, mov.to_san(&board));
+            board.make_move(mov).unwrap();
+            pgn_move
+        })
+        .collect()
+}
+
+#[test]
+fn writes_tags_movetext_and_result() {
+    let mut game = PgnGame::new();
+    game.set_tag("White", "Anderssen, Adolf");
+    game.set_tag("Annotator", "Club \"archive\"");
+    game.set_tag(
+        "FEN",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    );
+    let board = game.starting_board().unwrap();
+
+    game.moves = line(&board, &["Bb5", "a6", "Ba4", "Nf6"]);
+    game.moves[0].clock = Some(Duration::from_secs(5400));
+    game.moves[0].comments.push("The Spanish".to_string());
+    game.moves[1].nags.push(1);
+    game.moves[1].clock = Some(Duration::from_millis(5_309_500));
+
+    let mut after_a6 = board.clone();
+    for pgn_move in &game.moves[..2] {
+        after_a6.make_move(pgn_move.mov).unwrap();
+    }
+    game.moves[2]
+        .variations
+        .push(line(&after_a6, &["Bxc6", "dxc6"]));
+    game.result = GameResult::Draw;
+
+    assert_eq!(
+        game.to_pgn(),
+        r#"[Event "?"]
+[Site "?"]
+[Date "????.??.??"]
+[Round "?"]
+[White "Anderssen, Adolf"]
+[Black "?"]
+[Result "1/2-1/2"]
+[Annotator "Club \"archive\""]
+[SetUp "1"]
+[FEN "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3"]
+
+3. Bb5 {[%clk 1:30:00]} {The Spanish} 3... a6 $1 {[%clk 1:28:29.5]} 4. Ba4
+(4. Bxc6 dxc6) 4... Nf6 1/2-1/2
+"#
+    );
+
+    // the moves and their annotations read back the same
+    let read = PgnGame::from_pgn(&game.to_pgn()).unwrap();
+    assert_eq!(read.moves, game.moves);
+    assert_eq!(read.tag("SetUp"), Some("1"));
+}
+
+#[test]
+fn leaves_closing_braces_out_of_comments() {
+    let mut game = PgnGame::new();
+    game.moves = line(&Board::new_arranged(), &["e4", "e5"]);
+    game.moves[0].comments.push("look } here".to_string());
+    game.moves[1].comments_before.push("}".to_string());
+    game.moves[1].comments.push("{nested}".to_string());
+
+    let read = PgnGame::from_pgn(&game.to_pgn()).unwrap();
+    assert_eq!(read.moves[0].comments, ["look here"]);
+    assert!(read.moves[1].comments_before.is_empty());
+    assert_eq!(read.moves[1].comments, ["{nested"]);
+}
+
+#[test]
+fn round_trips_games_with_wrapped_lines() {
+    let games = read_pgn(OPERA_GAME).unwrap();
+    let pgn = write_pgn(&[games[0].clone(), games[0].clone()]);
+
+    assert!(pgn.lines().all(|line| line.len() <= 79));
+    let movetext = pgn.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert!(movetext.contains("b5 $6 (9... Qb4+ 10. Qxb4 Bxb4) 10. Nxb5 $1 cxb5"));
+    assert_eq!(
+        read_pgn(&pgn).unwrap(),
+        [games[0].clone(), games[0].clone()]
+    );
+}
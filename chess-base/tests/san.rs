@@ -0,0 +1,142 @@
+mod common;
+
+use chess_base::board::Board;
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::san::SanError;
+use common::{normal, square};
+
+/// Checks that the move is written as the text and that the text reads back as the move
+fn assert_san(fen: &str, mov: Move, san: &str) {
+    let board = Board::from_fen(fen).unwrap();
+    assert_eq!(mov.to_san(&board), san);
+    assert_eq!(Move::from_san(san, &board), Ok(mov));
+}
+
+#[test]
+fn writes_and_reads_simple_moves() {
+    let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    assert_san(start, normal("e2", "e4"), "e4");
+    assert_san(start, normal("g1", "f3"), "Nf3");
+
+    let fen = "rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+    assert_san(fen, normal("e4", "d5"), "exd5");
+    assert_san(fen, normal("f1", "b5"), "Bb5+");
+}
+
+#[test]
+fn disambiguates_by_file_rank_or_both() {
+    let knights = "rnbqkb1r/ppp2ppp/4pn2/3p4/2PP4/2N5/PP2PPPP/R1BQKBNR b KQkq - 1 4";
+    assert_san(knights, normal("b8", "d7"), "Nbd7");
+    assert_san(knights, normal("f6", "d7"), "Nfd7");
+
+    let rooks = "3k4/8/8/8/8/4R3/8/4R1K1 w - - 0 1";
+    assert_san(rooks, normal("e1", "e2"), "R1e2");
+    assert_san(rooks, normal("e3", "e2"), "R3e2");
+
+    let queens = "4k3/8/8/8/8/Q7/8/Q1Q3K1 w - - 0 1";
+    assert_san(queens, normal("a1", "b2"), "Qa1b2");
+    assert_san(queens, normal("a3", "b2"), "Q3b2");
+    assert_san(queens, normal("c1", "b2"), "Qcb2");
+}
+
+#[test]
+fn writes_and_reads_special_moves() {
+    let promotion = "3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    let promote = |to, promotion| Move::Promotion {
+        from: square("e7"),
+        to: square(to),
+        promotion,
+    };
+    assert_san(promotion, promote("e8", Pieces::Queen), "e8=Q+");
+    assert_san(promotion, promote("d8", Pieces::Knight), "exd8=N");
+    assert_san(promotion, promote("d8", Pieces::Queen), "exd8=Q+");
+
+    let en_passant = "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3";
+    let mov = Move::EnPassant {
+        from: square("e5"),
+        to: square("f6"),
+    };
+    assert_san(en_passant, mov, "exf6");
+
+    let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let castle = |castle_type| Move::Castle {
+        color: Color::White,
+        castle_type,
+    };
+    assert_san(kiwipete, castle(Castles::KingSide), "O-O");
+    assert_san(kiwipete, castle(Castles::QueenSide), "O-O-O");
+
+    let fools_mate = "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2";
+    assert_san(fools_mate, normal("d8", "h4"), "Qh4#");
+}
+
+#[test]
+fn reads_common_variations() {
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    let castle = Move::Castle {
+        color: Color::White,
+        castle_type: Castles::KingSide,
+    };
+    assert_eq!(Move::from_san("0-0", &board), Ok(castle));
+    assert_eq!(Move::from_san("O-O+", &board), Ok(castle));
+    assert_eq!(Move::from_san(" Nxf7!? ", &board), Ok(normal("e5", "f7")));
+    assert_eq!(Move::from_san("Ne5xf7", &board), Ok(normal("e5", "f7")));
+
+    let board = Board::from_fen("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    let mov = Move::Promotion {
+        from: square("e7"),
+        to: square("e8"),
+        promotion: Pieces::Rook,
+    };
+    assert_eq!(Move::from_san("e8R", &board), Ok(mov));
+}
+
+#[test]
+fn rejects_bad_input() {
+    let board = Board::from_fen("rnbqkb1r/ppp2ppp/4pn2/3p4/2PP4/2N5/PP2PPPP/R1BQKBNR b KQkq - 1 4")
+        .unwrap();
+
+    assert_eq!(Move::from_san("Nd7", &board), Err(SanError::AmbiguousMove));
+    assert_eq!(Move::from_san("e4", &board), Err(SanError::IllegalMove));
+    assert_eq!(Move::from_san("Ke7", &board), Ok(normal("e8", "e7")));
+    assert_eq!(Move::from_san("Kf7", &board), Err(SanError::IllegalMove));
+    assert_eq!(Move::from_san("O-O", &board), Err(SanError::IllegalMove));
+    assert_eq!(Move::from_san("", &board), Err(SanError::InvalidSyntax));
+    assert_eq!(
+        Move::from_san("hello", &board),
+        Err(SanError::InvalidSyntax)
+    );
+    assert_eq!(
+        Move::from_san("Nb8d7x", &board),
+        Err(SanError::InvalidSyntax)
+    );
+    assert_eq!(Move::from_san("Qe9", &board), Err(SanError::InvalidSyntax));
+
+    // a promotion has to say what the pawn becomes
+    let board = Board::from_fen("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+    assert_eq!(Move::from_san("e8", &board), Err(SanError::IllegalMove));
+    assert_eq!(Move::from_san("e8=K", &board), Err(SanError::InvalidSyntax));
+}
+
+#[test]
+fn every_legal_move_round_trips() {
+    let positions = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ];
+
+    for fen in positions {
+        let board = Board::from_fen(fen).unwrap();
+        for mov in board.get_all_legal_moves_for(board.get_turn()) {
+            let san = mov.to_san(&board);
+            assert_eq!(Move::from_san(&san, &board), Ok(mov), "{} in {}", san, fen);
+        }
+    }
+}
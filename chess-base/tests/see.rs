@@ -0,0 +1,91 @@
+use chess_base::board::Board;
+use chess_base::core::mov::Move;
+use chess_base::core::position::Position;
+
+/// Returns the exchange evaluation of a move in standard algebraic notation
+fn see(fen: &str, san: &str) -> i32 {
+    let board = Board::from_fen(fen).unwrap();
+    board.see(Move::from_san(san, &board).unwrap())
+}
+
+#[test]
+fn free_and_defended_pieces() {
+    // an undefended rook just wins the pawn
+    assert_eq!(
+        see("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "Rxe5"),
+        100
+    );
+    // a queen taking a pawn that a pawn defends
+    assert_eq!(see("4k3/8/3p4/4p3/8/8/8/4QK2 w - - 0 1", "Qxe5+"), -800);
+    // a pawn taking a defended knight still wins material
+    assert_eq!(see("4k3/8/3p4/4n3/3P4/8/8/4K3 w - - 0 1", "dxe5"), 200);
+}
+
+#[test]
+fn pieces_behind_each_other_join_in() {
+    // knight, knight, rook, bishop, queen and queen all take on e5 in turn,
+    // the rook and both queens only see the square once the piece in front of them has moved
+    assert_eq!(
+        see(
+            "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1",
+            "Nxe5"
+        ),
+        -200
+    );
+    // doubled rooks win a pawn defended once
+    assert_eq!(see("4k3/4r3/8/4p3/8/8/4R3/4R1K1 w - - 0 1", "Rxe5"), 100);
+}
+
+#[test]
+fn kings_only_take_undefended_pieces() {
+    assert_eq!(see("4k3/8/8/8/8/8/3q4/4K3 w - - 0 1", "Kxd2"), 900);
+    // an illegal move, which the exchange evaluation does not check
+    let board = Board::from_fen("4k3/8/8/3r4/8/8/3q4/4K3 w - - 0 1").unwrap();
+    let mov = Move::Normal {
+        from: Position::from_an('e', 1),
+        to: Position::from_an('d', 2),
+    };
+    assert_eq!(board.see(mov), -20_000 + 900);
+
+    // a move off the board is not looked at either, it wins nothing
+    let board = Board::new_arranged();
+    let off_board = [
+        Move::Normal {
+            from: Position::from_an('e', 2),
+            to: Position::new(4, 9),
+        },
+        Move::EnPassant {
+            from: Position::new(12, 4),
+            to: Position::from_an('d', 6),
+        },
+    ];
+    for mov in off_board {
+        assert_eq!(board.see(mov), 0);
+    }
+}
+
+#[test]
+fn special_moves() {
+    // en passant takes a pawn that is not on the square the pawn moves to
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6"), 100);
+    // a promotion wins the difference between the new piece and the pawn
+    assert_eq!(see("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a8=Q+"), 800);
+    assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a8=Q"), -100);
+    assert_eq!(see("r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1", "O-O"), 0);
+}
+
+#[test]
+fn quiet_moves_onto_attacked_squares() {
+    let board = Board::new_arranged();
+    assert_eq!(board.see(Move::from_san("e4", &board).unwrap()), 0);
+    assert!(!board.is_capture(Move::from_san("e4", &board).unwrap()));
+
+    // the queen can be taken by the knight on h5
+    assert_eq!(
+        see(
+            "rnbqkb1r/pppppppp/5n2/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2",
+            "Qh5"
+        ),
+        -900
+    );
+}
@@ -0,0 +1,116 @@
+use chess_base::board::Board;
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use chess_base::core::uci::ParseMoveError;
+
+#[test]
+fn writes_moves() {
+    let mov = Move::Normal {
+        from: Position::from_an('e', 2),
+        to: Position::from_an('e', 4),
+    };
+    assert_eq!(mov.to_string(), "e2e4");
+
+    let mov = Move::Promotion {
+        from: Position::from_an('e', 7),
+        to: Position::from_an('e', 8),
+        promotion: Pieces::Knight,
+    };
+    assert_eq!(mov.to_string(), "e7e8n");
+
+    let castles = [
+        (Color::White, Castles::KingSide, "e1g1"),
+        (Color::White, Castles::QueenSide, "e1c1"),
+        (Color::Black, Castles::KingSide, "e8g8"),
+        (Color::Black, Castles::QueenSide, "e8c8"),
+    ];
+    for (color, castle_type, uci) in castles {
+        assert_eq!(Move::Castle { color, castle_type }.to_string(), uci);
+    }
+
+    assert_eq!(Pieces::Knight.to_string(), "N");
+}
+
+#[test]
+fn reads_moves() {
+    assert_eq!(
+        "e2e4".parse(),
+        Ok(Move::Normal {
+            from: Position::from_an('e', 2),
+            to: Position::from_an('e', 4),
+        })
+    );
+    assert_eq!(
+        "a2a1q".parse(),
+        Ok(Move::Promotion {
+            from: Position::from_an('a', 2),
+            to: Position::from_an('a', 1),
+            promotion: Pieces::Queen,
+        })
+    );
+
+    assert_eq!("e2e".parse::<Move>(), Err(ParseMoveError::InvalidSyntax));
+    assert_eq!("e2e4qq".parse::<Move>(), Err(ParseMoveError::InvalidSyntax));
+    assert_eq!("i2e4".parse::<Move>(), Err(ParseMoveError::InvalidSyntax));
+    assert_eq!("e0e4".parse::<Move>(), Err(ParseMoveError::InvalidSyntax));
+    assert_eq!(
+        "e7e8k".parse::<Move>(),
+        Err(ParseMoveError::InvalidPromotion('k'))
+    );
+}
+
+#[test]
+fn resolves_castling_and_en_passant_on_the_board() {
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1")
+            .unwrap();
+    let resolve = |uci: &str| board.resolve_move(uci.parse().unwrap());
+
+    assert_eq!(
+        resolve("e8g8"),
+        Move::Castle {
+            color: Color::Black,
+            castle_type: Castles::KingSide,
+        }
+    );
+    assert_eq!(
+        resolve("e8c8"),
+        Move::Castle {
+            color: Color::Black,
+            castle_type: Castles::QueenSide,
+        }
+    );
+    assert_eq!(
+        resolve("b4a3"),
+        Move::EnPassant {
+            from: Position::from_an('b', 4),
+            to: Position::from_an('a', 3),
+        }
+    );
+    assert_eq!(resolve("e8f8"), "e8f8".parse().unwrap());
+    assert_eq!(resolve("b4c3"), "b4c3".parse().unwrap());
+}
+
+#[test]
+fn every_legal_move_round_trips() {
+    let positions = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    ];
+
+    for fen in positions {
+        let mut board = Board::from_fen(fen).unwrap();
+        for mov in board.get_all_legal_moves_for(board.get_turn()) {
+            let uci = mov.to_string();
+            assert_eq!(board.resolve_move(uci.parse().unwrap()), mov, "{}", uci);
+        }
+
+        // make_move resolves the move by itself
+        let mov = board.get_all_legal_moves_for(board.get_turn())[0];
+        board.make_move(mov.to_string().parse().unwrap()).unwrap();
+    }
+}
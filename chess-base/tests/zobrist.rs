@@ -0,0 +1,83 @@
+mod common;
+
+use chess_base::board::Board;
+use common::normal;
+
+/// Plays the moves from the start position, checking that the incrementally updated hash
+/// matches the hash of the same position parsed from its FEN after every move
+fn play(moves: &[(&str, &str)]) -> Board {
+    let mut board = Board::new_arranged();
+    for (from, to) in moves {
+        board.make_move(normal(from, to)).unwrap();
+        assert_eq!(
+            board.hash(),
+            Board::from_fen(&board.to_fen()).unwrap().hash(),
+            "after {}{}",
+            from,
+            to
+        );
+    }
+    board
+}
+
+#[test]
+fn matches_the_polyglot_keys() {
+    // the test positions from the Polyglot book format documentation
+    assert_eq!(Board::new_arranged().hash(), 0x463b96181691fc9c);
+
+    let mut moves = Vec::new();
+    let expected = [
+        (("e2", "e4"), 0x823c9b50fd114196),
+        (("d7", "d5"), 0x0756b94461c50fb0),
+        (("e4", "e5"), 0x662fafb965db29d4),
+        (("f7", "f5"), 0x22a48b5a8e47ff78),
+        (("e1", "e2"), 0x652a607ca3f242c1),
+        (("e8", "f7"), 0x00fdd303c946bdd9),
+    ];
+    for (mov, hash) in expected {
+        moves.push(mov);
+        assert_eq!(play(&moves).hash(), hash);
+    }
+
+    let board = play(&[
+        ("a2", "a4"),
+        ("b7", "b5"),
+        ("h2", "h4"),
+        ("b5", "b4"),
+        ("c2", "c4"),
+    ]);
+    assert_eq!(board.hash(), 0x3c8123ea7b067637);
+
+    let board = play(&[
+        ("a2", "a4"),
+        ("b7", "b5"),
+        ("h2", "h4"),
+        ("b5", "b4"),
+        ("c2", "c4"),
+        ("b4", "c3"),
+        ("a1", "a3"),
+    ]);
+    assert_eq!(board.hash(), 0x5c3f9b829b279560);
+}
+
+#[test]
+fn follows_every_kind_of_move() {
+    let mut board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/Pp2P3/2N2Q1p/1PPBBPPP/R3K2R b KQkq a3 0 1")
+            .unwrap();
+    let hash = board.hash();
+
+    for mov in board.get_all_legal_moves_for_black() {
+        let undo = board.make_move_no_checks(mov);
+        let fen = board.to_fen();
+        assert_eq!(
+            board.hash(),
+            Board::from_fen(&fen).unwrap().hash(),
+            "{}",
+            fen
+        );
+        assert_ne!(board.hash(), hash);
+        board.unmake_move(undo);
+        assert_eq!(board.hash(), hash);
+    }
+}
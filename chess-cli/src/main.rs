@@ -1,19 +1,21 @@
 use chess_base as base;
 
-use base::board::*;
-use base::core::castles::Castles;
 use base::core::color::Color;
 use base::core::mov::Move;
-use base::core::pieces::Pieces;
-use base::core::position::Position;
+use base::game::Game;
+use chess_engine::search::{SearchLimits, Searcher, DEFAULT_HASH_MB, MAX_HASH_MB};
 use colored::*;
 use fern::Dispatch;
 use log::Level;
 use log::LevelFilter;
-use log::{debug, info};
+use log::{debug, info, warn};
 use std::io;
 use std::process::Command;
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the engine thinks about a move
+const ENGINE_TIME: Duration = Duration::from_secs(2);
 
 fn setup_logger(log_level: LevelFilter) -> Result<(), fern::InitError> {
     let console_logger = Dispatch::new()
@@ -45,6 +47,7 @@ fn setup_logger(log_level: LevelFilter) -> Result<(), fern::InitError> {
         .level(LevelFilter::Off)
         .level_for("chess_base", log_level)
         .level_for("chess_cli", log_level)
+        .level_for("chess_engine", log_level)
         .chain(io::stdout());
     //or stderr
 
@@ -57,13 +60,14 @@ fn setup_logger(log_level: LevelFilter) -> Result<(), fern::InitError> {
 }
 
 fn render_game(fen: String) {
-    let x: Vec<&str> = fen.split("/").collect();
+    // the piece placement field lists the ranks from the eighth down to the first
+    let placement = fen.split(' ').next().unwrap_or_default();
 
-    for rank in (0..=7).rev() {
-        print!("{}", (rank + 1).to_string().red());
+    for (i, rank) in placement.split('/').enumerate() {
+        print!("{}", (8 - i).to_string().red());
 
-        for char in x[rank].chars() {
-            if char.is_digit(10) {
+        for char in rank.chars() {
+            if char.is_ascii_digit() {
                 for _ in 0..char.to_digit(10).unwrap() {
                     print!(" • ");
                 }
@@ -121,54 +125,119 @@ fn render_game(fen: String) {
     println!();
 }
 
-/// Makes a move on the board and re-renders the game
-fn make_move(board: &mut Board, mov: Move) {
+/// Clears the terminal and renders the board at the cursor of the game,
+/// with the moves that led to it listed below the board
+fn render(game: &Game) {
     #[cfg(target_os = "linux")]
     let _ = Command::new("clear").status();
     #[cfg(target_os = "windows")]
     let _ = Command::new("cls").status();
 
-    board.make_move(mov);
-    render_game(board.generate_fen());
+    render_game(game.get_board().to_fen());
+    render_moves(game);
+}
+
+/// Makes a move in the game and re-renders it
+/// The time the player spent on the move is recorded with it
+fn make_move(game: &mut Game, mov: Move, time_spent: Duration) {
+    let result = game.make_move(mov);
+    if result.is_ok() {
+        let cursor = game.cursor();
+        game.node_mut(cursor).time_spent = Some(time_spent);
+    }
+
+    render(game);
+
+    if let Err(error) = result {
+        warn!("Illegal move: {}", error);
+    }
+}
+
+/// Prints the moves up to the cursor in pairs, numbered like a score sheet
+fn render_moves(game: &Game) {
+    let played = &game.current_line()[..game.ply()];
+    for (i, pair) in played.chunks(2).enumerate() {
+        let sans: Vec<&str> = pair.iter().map(|&id| game.node(id).get_san()).collect();
+        println!("{}. {}", i + 1, sans.join(" "));
+    }
+}
+
+/// Lets the engine pick a move for the side to move and plays it
+fn engine_move(game: &mut Game, searcher: &mut Searcher) {
+    let start = Instant::now();
+    let result = searcher.search(game.get_board(), SearchLimits::time(ENGINE_TIME));
+
+    let Some(mov) = result.best_move else {
+        return;
+    };
+    let san = mov.to_san(game.get_board());
+    make_move(game, mov, start.elapsed());
+
+    match result.mate_in() {
+        Some(moves) => info!("Engine played {} (mate in {})", san, moves),
+        None => info!(
+            "Engine played {} (score {}, depth {}, {} nodes, hash {}/1000 full)",
+            san, result.score, result.depth, result.nodes, result.hashfull
+        ),
+    }
+}
+
+/// Writes the game with its result so far to a PGN file
+fn save_game(game: &Game, path: &str) {
+    let mut pgn = game.to_pgn();
+    pgn.set_tag("Event", "Casual game");
+    pgn.set_tag("Site", "chess-cli");
+    pgn.set_tag("Date", &chrono::Local::now().format("%Y.%m.%d").to_string());
+
+    match std::fs::write(path, pgn.to_pgn()) {
+        Ok(()) => info!("Saved the game to {}", path),
+        Err(error) => warn!("Could not save the game to {}: {}", path, error),
+    }
 }
 
 /// Starts the game
 /// Using a default board configuration
-pub fn start_game() {
-    let mut board = Board::new_arranged();
+/// The engine plays the given color, or only moves when asked with "go",
+/// with a transposition table of the given size in megabytes
+pub fn start_game(engine: Option<Color>, hash_mb: usize) {
+    let mut game = Game::new();
+    let mut searcher = Searcher::new();
+    searcher.set_hash_size(hash_mb);
+    let mut thinking = Instant::now();
 
-    render_game(board.generate_fen());
+    render_game(game.get_board().to_fen());
 
     loop {
+        let board = game.get_board();
         debug!(
             "number of moves ==> depth1: {} depth2: {}",
-            board.calculate_nr_of_moves_with_depth(1),
-            board.calculate_nr_of_moves_with_depth(2)
+            board.perft(1),
+            board.perft(2)
         );
         // debug!(
         //     "number of moves ==> depth3: {} depth4: {}",
-        //     board.calculate_nr_of_moves_with_depth(3),
-        //     board.calculate_nr_of_moves_with_depth(4)
+        //     board.perft(3),
+        //     board.perft(4)
         // );
-        match board.get_turn() {
-            Color::White => {
-                if board.in_checkmate(Color::White) {
-                    info!("Black wins by checkmate");
-                    break;
-                } else if board.in_stalemate(Color::White) {
-                    info!("Stalemate");
-                    break;
-                }
-            }
-            Color::Black => {
-                if board.in_checkmate(Color::Black) {
-                    info!("White wins by checkmate");
-                    break;
-                } else if board.in_stalemate(Color::Black) {
-                    info!("Stalemate");
-                    break;
-                }
+        let status = game.status();
+        if status.is_over() {
+            info!("Game over: {}", status);
+            println!("\nenter a file name to save the game, or nothing to quit:");
+
+            let mut path = String::new();
+            std::io::stdin()
+                .read_line(&mut path)
+                .expect("Failed to read line");
+            if !path.trim().is_empty() {
+                save_game(&game, path.trim());
             }
+            break;
+        }
+
+        if board.is_threefold_repetition() {
+            info!("The position occurred three times, enter \"draw\" to claim a draw");
+        } else if board.can_claim_fifty_move_draw() {
+            info!("Fifty moves without a capture or pawn move, enter \"draw\" to claim a draw");
         }
 
         match board.get_turn() {
@@ -176,90 +245,130 @@ pub fn start_game() {
             Color::Black => info!("Black to move"),
         }
 
-        println!("\nenter move:");
+        if engine == Some(board.get_turn()) {
+            engine_move(&mut game, &mut searcher);
+            thinking = Instant::now();
+            continue;
+        }
+
+        println!("\nenter move (like e4, Nf3, exd5, O-O, e8=Q or e2e4):");
 
         let mut input = String::new();
         std::io::stdin()
             .read_line(&mut input)
             .expect("Failed to read line");
 
-        let turn = board.get_turn();
-
-        let promotions = vec!["=Q", "=R", "=B", "=Kn"];
-
-        for pat in promotions {
-            if input.contains(pat) {
-                let input: Vec<&str> = input.split_whitespace().collect();
-
-                let from = Position::from_an(
-                    input[0].chars().nth(0).unwrap(),
-                    input[0].chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-                );
-                let to = Position::from_an(
-                    input[1].chars().nth(0).unwrap(),
-                    input[1].chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-                );
-
-                let piece_type;
+        if input.trim() == "draw" {
+            if game.claim_draw().is_none() {
+                warn!("A draw can not be claimed in this position");
+            }
+            continue;
+        }
 
-                match pat {
-                    "=Q" => piece_type = Pieces::Queen,
-                    "=R" => piece_type = Pieces::Rook,
-                    "=B" => piece_type = Pieces::Bishop,
-                    "=Kn" => piece_type = Pieces::Knight,
-                    _ => panic!("Invalid promotion"),
+        // "perft <depth>" lists the node count of every move, to debug the move generation
+        if let Some(depth) = input.trim().strip_prefix("perft ") {
+            match depth.parse() {
+                Ok(depth) => {
+                    let divide = game.get_board().perft_divide(depth);
+                    for (mov, nodes) in &divide {
+                        info!("{:?}: {}", mov, nodes);
+                    }
+                    info!(
+                        "total: {}",
+                        divide.iter().map(|(_, nodes)| nodes).sum::<u64>()
+                    );
                 }
-
-                let mov = Move::Promotion {
-                    from,
-                    to,
-                    promotion: piece_type,
-                };
-
-                make_move(&mut board, mov);
-                continue;
+                Err(_) => warn!("Invalid depth: {}", depth),
             }
+            continue;
         }
 
-        let queenside = input.contains("O-O-O");
+        // "save <file>" writes the game so far to a PGN file
+        if let Some(path) = input.trim().strip_prefix("save ") {
+            save_game(&game, path.trim());
+            continue;
+        }
 
-        if queenside {
-            let mov = Move::Castle {
-                color: turn,
-                castle_type: Castles::QueenSide,
+        // "undo" takes back the last move, "redo" plays it again
+        if input.trim() == "undo" || input.trim() == "redo" {
+            let moved = match input.trim() {
+                "undo" => game.undo(),
+                _ => game.redo(),
             };
-            make_move(&mut board, mov);
+            render(&game);
+            if !moved {
+                warn!("There is no move to {}", input.trim());
+            }
+            thinking = Instant::now();
             continue;
         }
 
-        let kingside = input.contains("O-O");
-
-        if kingside {
-            let mov = Move::Castle {
-                color: turn,
-                castle_type: Castles::KingSide,
-            };
-            make_move(&mut board, mov);
+        // "go" lets the engine play a move for the side to move
+        if input.trim() == "go" {
+            engine_move(&mut game, &mut searcher);
+            thinking = Instant::now();
             continue;
         }
 
-        let input: Vec<&str> = input.split_whitespace().collect();
+        if input.trim() == "resign" {
+            let turn = game.get_board().get_turn();
+            game.resign(turn);
+            continue;
+        }
 
-        let from = Position::from_an(
-            input[0].chars().nth(0).unwrap(),
-            input[0].chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-        );
-        let to = Position::from_an(
-            input[1].chars().nth(0).unwrap(),
-            input[1].chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-        );
-        let mov = Move::Normal { from, to };
+        // moves in coordinate form like e2e4 are accepted as well
+        let board = game.get_board();
+        let mov = match Move::from_san(input.trim(), board) {
+            Ok(mov) => mov,
+            Err(error) => match input.trim().parse::<Move>() {
+                Ok(mov) => board.resolve_move(mov),
+                Err(_) => {
+                    warn!("Invalid move \"{}\": {}", input.trim(), error);
+                    continue;
+                }
+            },
+        };
 
-        make_move(&mut board, mov);
+        make_move(&mut game, mov, thinking.elapsed());
+        thinking = Instant::now();
     }
 }
 
 fn main() {
     setup_logger(LevelFilter::Trace).unwrap();
-    start_game();
+
+    // "--engine white" or "--engine black" lets the engine play that color
+    let args: Vec<String> = std::env::args().collect();
+    let engine = match args.iter().position(|arg| arg == "--engine") {
+        Some(index) => match args.get(index + 1).map(String::as_str) {
+            Some("white") => Some(Color::White),
+            Some("black") => Some(Color::Black),
+            _ => {
+                warn!("Expected \"--engine white\" or \"--engine black\"");
+                return;
+            }
+        },
+        None => None,
+    };
+
+    // "--hash 64" gives the engine a transposition table of 64 MB
+    let hash_mb = match args.iter().position(|arg| arg == "--hash") {
+        Some(index) => match args
+            .get(index + 1)
+            .and_then(|mb| mb.parse().ok())
+            .filter(|mb| (1..=MAX_HASH_MB).contains(mb))
+        {
+            Some(mb) => mb,
+            None => {
+                warn!(
+                    "Expected the size of the hash in MB, from 1 to {}, like \"--hash 64\"",
+                    MAX_HASH_MB
+                );
+                return;
+            }
+        },
+        None => DEFAULT_HASH_MB,
+    };
+
+    start_game(engine, hash_mb);
 }
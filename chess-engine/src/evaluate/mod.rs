@@ -0,0 +1,203 @@
+/// Evaluate module
+/// Contains the static evaluation the search uses to score the positions at its horizon
+mod params;
+mod tables;
+
+pub use params::{EvalParams, Score};
+
+use chess_base::board::Board;
+use chess_base::core::attacks::{
+    bishop_attacks, king_attacks, knight_attacks, pawn_attacks, queen_attacks, rook_attacks,
+};
+use chess_base::core::bitboard::{Bitboards, Squares};
+use chess_base::core::color::Color;
+use chess_base::core::pieces::Pieces;
+
+/// The piece types in the order the arrays of EvalParams use
+const PIECES: [Pieces; 6] = [
+    Pieces::Pawn,
+    Pieces::Knight,
+    Pieces::Bishop,
+    Pieces::Rook,
+    Pieces::Queen,
+    Pieces::King,
+];
+
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+const RANK_1: u64 = 0xff;
+
+/// Scores a position without looking ahead
+pub trait Evaluator {
+    /// Returns the score of the position in centipawns from the point of view of the side to move
+    fn evaluate(&self, board: &Board) -> i32;
+}
+
+/// The evaluation the engine uses by default
+/// It adds up material, piece-square tables, mobility, king safety and pawn structure,
+/// each with a middlegame and an endgame weight that are blended by the material left
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StandardEvaluator {
+    pub params: EvalParams,
+}
+
+impl StandardEvaluator {
+    pub fn new(params: EvalParams) -> StandardEvaluator {
+        StandardEvaluator { params }
+    }
+
+    /// Returns the middlegame and endgame scores from the point of view of white
+    pub fn score(&self, board: &Board) -> Score {
+        let bitboards = board.get_bitboards();
+        self.side(bitboards, Color::White) - self.side(bitboards, Color::Black)
+    }
+
+    /// Returns how far the game is from the endgame, see EvalParams::phase_weights
+    pub fn phase(&self, board: &Board) -> i32 {
+        let bitboards = board.get_bitboards();
+        PIECES
+            .iter()
+            .zip(self.params.phase_weights)
+            .map(|(&piece_type, weight)| {
+                let count = bitboards.pieces(Color::White, piece_type).count_ones()
+                    + bitboards.pieces(Color::Black, piece_type).count_ones();
+                count as i32 * weight
+            })
+            .sum()
+    }
+
+    /// Returns the score of the pieces of one color
+    fn side(&self, bitboards: &Bitboards, color: Color) -> Score {
+        let params = &self.params;
+        let enemy = color.opponent();
+        let occupancy = bitboards.all();
+        let own = bitboards.color(color);
+
+        let enemy_pawn_attacks = Squares(bitboards.pieces(enemy, Pieces::Pawn))
+            .fold(0, |attacks, square| attacks | pawn_attacks(square, enemy));
+        let enemy_king_zone = Squares(bitboards.pieces(enemy, Pieces::King))
+            .fold(0, |zone, square| zone | king_attacks(square));
+
+        let mut score = Score::default();
+        for (index, &piece_type) in PIECES.iter().enumerate() {
+            for square in Squares(bitboards.pieces(color, piece_type)) {
+                score += params.piece_values[index];
+                score += params.psts[index][relative_square(square, color)];
+
+                let attacks = match piece_type {
+                    Pieces::Knight => knight_attacks(square),
+                    Pieces::Bishop => bishop_attacks(square, occupancy),
+                    Pieces::Rook => rook_attacks(square, occupancy),
+                    Pieces::Queen => queen_attacks(square, occupancy),
+                    Pieces::Pawn | Pieces::King => 0,
+                };
+                let safe = attacks & !own & !enemy_pawn_attacks;
+                score += params.mobility[index] * safe.count_ones() as i32;
+                // the penalty belongs to the enemy, so it counts for this side
+                score -= params.king_zone_attack * (attacks & enemy_king_zone).count_ones() as i32;
+            }
+        }
+
+        if bitboards.pieces(color, Pieces::Bishop).count_ones() >= 2 {
+            score += params.bishop_pair;
+        }
+
+        score + self.pawn_structure(bitboards, color) + self.king_shield(bitboards, color)
+    }
+
+    fn pawn_structure(&self, bitboards: &Bitboards, color: Color) -> Score {
+        let params = &self.params;
+        let pawns = bitboards.pieces(color, Pieces::Pawn);
+        let enemy_pawns = bitboards.pieces(color.opponent(), Pieces::Pawn);
+        let mut score = Score::default();
+
+        for file in 0..8 {
+            let count = (pawns & (FILE_A << file)).count_ones() as i32;
+            if count > 1 {
+                score += params.doubled_pawn * (count - 1);
+            }
+        }
+
+        for square in Squares(pawns) {
+            let (file, rank) = (square % 8, square / 8);
+            let neighbours = neighbour_files(file);
+
+            if pawns & neighbours == 0 {
+                score += params.isolated_pawn;
+            }
+
+            let front = (neighbours | FILE_A << file) & ranks_ahead(rank, color);
+            if enemy_pawns & front == 0 {
+                score += params.passed_pawn[relative_rank(rank, color)];
+            }
+        }
+
+        score
+    }
+
+    fn king_shield(&self, bitboards: &Bitboards, color: Color) -> Score {
+        let Some(square) = Squares(bitboards.pieces(color, Pieces::King)).next() else {
+            return Score::default();
+        };
+        let (file, rank) = (square % 8, square / 8);
+        let ahead = |n: usize| match color {
+            Color::White => rank_mask(rank + n),
+            Color::Black => rank.checked_sub(n).map_or(0, rank_mask),
+        };
+        let shield = (neighbour_files(file) | FILE_A << file) & (ahead(1) | ahead(2));
+        let pawns = bitboards.pieces(color, Pieces::Pawn) & shield;
+
+        self.params.king_shield * pawns.count_ones() as i32
+    }
+}
+
+impl Evaluator for StandardEvaluator {
+    fn evaluate(&self, board: &Board) -> i32 {
+        let score = self
+            .score(board)
+            .taper(self.phase(board), self.params.max_phase());
+        match board.get_turn() {
+            Color::White => score,
+            Color::Black => -score,
+        }
+    }
+}
+
+/// Returns the index into the piece-square tables, which are written from a8 to h1 for white
+fn relative_square(square: usize, color: Color) -> usize {
+    match color {
+        Color::White => square ^ 56,
+        Color::Black => square,
+    }
+}
+
+/// Returns the rank as seen from the side of the color, 0 being its back rank
+fn relative_rank(rank: usize, color: Color) -> usize {
+    match color {
+        Color::White => rank,
+        Color::Black => 7 - rank,
+    }
+}
+
+fn rank_mask(rank: usize) -> u64 {
+    if rank < 8 {
+        RANK_1 << (8 * rank)
+    } else {
+        0
+    }
+}
+
+/// Returns the files left and right of the given file
+fn neighbour_files(file: usize) -> u64 {
+    let left = if file > 0 { FILE_A << (file - 1) } else { 0 };
+    let right = if file < 7 { FILE_A << (file + 1) } else { 0 };
+    left | right
+}
+
+/// Returns all the squares in front of the rank, as seen from the side of the color
+fn ranks_ahead(rank: usize, color: Color) -> u64 {
+    match color {
+        Color::White if rank >= 7 => 0,
+        Color::White => !0 << (8 * (rank + 1)),
+        Color::Black => (1 << (8 * rank)) - 1,
+    }
+}
@@ -0,0 +1,161 @@
+/// Params module
+/// Contains the weights of the evaluation terms, which can be changed to tune the evaluation
+use super::tables;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A score with a middlegame and an endgame part
+/// The evaluation blends the two by how much material is left on the board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Score {
+    pub mg: i32,
+    pub eg: i32,
+}
+
+impl Score {
+    pub const fn new(mg: i32, eg: i32) -> Score {
+        Score { mg, eg }
+    }
+
+    /// Blends the two parts, phase goes from 0 in a bare endgame to max_phase with all pieces on the board
+    pub fn taper(&self, phase: i32, max_phase: i32) -> i32 {
+        let max_phase = max_phase.max(1);
+        let phase = phase.clamp(0, max_phase);
+        (self.mg * phase + self.eg * (max_phase - phase)) / max_phase
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, other: Score) -> Score {
+        Score::new(self.mg + other.mg, self.eg + other.eg)
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    fn sub(self, other: Score) -> Score {
+        Score::new(self.mg - other.mg, self.eg - other.eg)
+    }
+}
+
+impl Mul<i32> for Score {
+    type Output = Score;
+
+    fn mul(self, n: i32) -> Score {
+        Score::new(self.mg * n, self.eg * n)
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    fn neg(self) -> Score {
+        Score::new(-self.mg, -self.eg)
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, other: Score) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Score {
+    fn sub_assign(&mut self, other: Score) {
+        *self = *self - other;
+    }
+}
+
+/// The weights of the evaluation terms, all in centipawns
+/// The arrays indexed by piece type are in the order of Pieces: pawn, knight, bishop, rook, queen, king.
+/// Setting the weights of a term to zero turns it off
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalParams {
+    /// The value of every piece type
+    pub piece_values: [Score; 6],
+    /// The bonus for a piece on every square, for the white pieces from a8 to h1
+    /// The black pieces use the same tables with the ranks flipped
+    pub psts: [[Score; 64]; 6],
+    /// How much every piece type counts towards the middlegame, the phase is their sum
+    pub phase_weights: [i32; 6],
+    /// The bonus for having both bishops
+    pub bishop_pair: Score,
+    /// The bonus for every square a piece can move to that is not attacked by enemy pawns
+    pub mobility: [Score; 6],
+    /// The bonus for every pawn on the three files around the king, one or two ranks ahead of it
+    pub king_shield: Score,
+    /// The bonus for every square next to the king attacked by an enemy piece, usually negative
+    pub king_zone_attack: Score,
+    /// The bonus for every pawn on a file behind another pawn of the same color, usually negative
+    pub doubled_pawn: Score,
+    /// The bonus for a pawn without pawns of the same color on the files next to it, usually negative
+    pub isolated_pawn: Score,
+    /// The bonus for a pawn no enemy pawn can stop, by its rank as seen from its own side
+    pub passed_pawn: [Score; 8],
+}
+
+impl EvalParams {
+    /// Returns the phase of a board with all the pieces on it
+    pub fn max_phase(&self) -> i32 {
+        // 8 pawns, 2 knights, 2 bishops, 2 rooks, 1 queen and the king for each side
+        let counts = [8, 2, 2, 2, 1, 1];
+        2 * counts
+            .iter()
+            .zip(self.phase_weights)
+            .map(|(count, weight)| count * weight)
+            .sum::<i32>()
+    }
+}
+
+impl Default for EvalParams {
+    fn default() -> Self {
+        let pst = |mg: [i32; 64], eg: [i32; 64]| -> [Score; 64] {
+            std::array::from_fn(|index| Score::new(mg[index], eg[index]))
+        };
+
+        EvalParams {
+            piece_values: [
+                Score::new(100, 120),
+                Score::new(320, 300),
+                Score::new(330, 310),
+                Score::new(500, 530),
+                Score::new(950, 950),
+                Score::new(0, 0),
+            ],
+            psts: [
+                pst(tables::PAWN_MG, tables::PAWN_EG),
+                pst(tables::KNIGHT, tables::KNIGHT),
+                pst(tables::BISHOP, tables::BISHOP),
+                pst(tables::ROOK_MG, tables::ROOK_EG),
+                pst(tables::QUEEN, tables::QUEEN),
+                pst(tables::KING_MG, tables::KING_EG),
+            ],
+            phase_weights: [0, 1, 1, 2, 4, 0],
+            bishop_pair: Score::new(30, 50),
+            mobility: [
+                Score::new(0, 0),
+                Score::new(4, 4),
+                Score::new(5, 5),
+                Score::new(2, 4),
+                Score::new(1, 2),
+                Score::new(0, 0),
+            ],
+            king_shield: Score::new(10, 0),
+            king_zone_attack: Score::new(-8, 0),
+            doubled_pawn: Score::new(-10, -20),
+            isolated_pawn: Score::new(-10, -15),
+            passed_pawn: [
+                Score::new(0, 0),
+                Score::new(5, 10),
+                Score::new(5, 15),
+                Score::new(10, 25),
+                Score::new(20, 40),
+                Score::new(35, 70),
+                Score::new(60, 110),
+                Score::new(0, 0),
+            ],
+        }
+    }
+}
@@ -0,0 +1,42 @@
+/// Limits module
+/// Contains the limits that tell the search when to stop
+use std::time::Duration;
+
+/// When the search has to stop
+/// The search stops at whichever limit is reached first,
+/// without any limits it goes on until the maximum depth
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchLimits {
+    /// The number of moves to look ahead
+    pub depth: Option<u32>,
+    /// The number of positions to visit
+    pub nodes: Option<u64>,
+    /// How long to search
+    pub time: Option<Duration>,
+}
+
+impl SearchLimits {
+    /// Searches exactly the given number of moves ahead, but always at least one
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits {
+            depth: Some(depth),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Searches until the given number of positions was visited
+    pub fn nodes(nodes: u64) -> SearchLimits {
+        SearchLimits {
+            nodes: Some(nodes),
+            ..SearchLimits::default()
+        }
+    }
+
+    /// Searches for the given time
+    pub fn time(time: Duration) -> SearchLimits {
+        SearchLimits {
+            time: Some(time),
+            ..SearchLimits::default()
+        }
+    }
+}
@@ -0,0 +1,414 @@
+/// Search module
+/// Contains the search that picks a move: a negamax alpha-beta search that is started again
+/// one move deeper every time it finishes, until one of its limits is reached.
+/// At its horizon a quiescence search plays out the captures, so the positions it scores are quiet
+mod limits;
+mod ordering;
+mod transposition;
+
+pub use limits::SearchLimits;
+pub use ordering::{Heuristics, MovePicker};
+pub use transposition::{Bound, TranspositionTable, TtEntry, DEFAULT_HASH_MB, MAX_HASH_MB};
+
+use crate::evaluate::{Evaluator, StandardEvaluator};
+use chess_base::board::Board;
+use chess_base::core::mov::Move;
+use log::debug;
+use ordering::is_noisy;
+use std::time::Instant;
+
+/// The score of being checkmated on the board
+/// A mate that is further away scores one point less for every half move to it
+pub const MATE: i32 = 30_000;
+/// The most half moves the search looks ahead
+pub const MAX_PLY: usize = 128;
+
+/// Scores beyond this are mates
+const MATE_BOUND: i32 = MATE - MAX_PLY as i32;
+const INFINITY: i32 = MATE + 1;
+
+/// How many positions are visited between two looks at the clock
+const CLOCK_INTERVAL: u64 = 1024;
+
+/// What the search found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// The best move, None if the side to move has no legal moves
+    pub best_move: Option<Move>,
+    /// The score of the best move in centipawns, from the point of view of the side to move
+    pub score: i32,
+    /// The moves both sides are expected to play, starting with the best move
+    pub pv: Vec<Move>,
+    /// The number of positions visited
+    pub nodes: u64,
+    /// The depth of the last search that finished
+    pub depth: u32,
+    /// How full the transposition table is in permille
+    pub hashfull: u32,
+}
+
+impl SearchResult {
+    /// Returns the number of moves to the mate if the score is a mate,
+    /// positive if the side to move mates and negative if it gets mated
+    pub fn mate_in(&self) -> Option<i32> {
+        if self.score >= MATE_BOUND {
+            Some((MATE - self.score + 1) / 2)
+        } else if self.score <= -MATE_BOUND {
+            Some(-(MATE + self.score) / 2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Searches the position for the best move of the side to move
+pub fn search(board: &Board, limits: SearchLimits) -> SearchResult {
+    Searcher::new().search(board, limits)
+}
+
+/// Runs searches and keeps the state they share
+/// The positions at the end of the search are scored by the evaluator
+pub struct Searcher<E: Evaluator = StandardEvaluator> {
+    evaluator: E,
+    limits: SearchLimits,
+    start: Instant,
+    nodes: u64,
+    /// Set once the first depth is done, there is no move to play before that
+    can_stop: bool,
+    stopped: bool,
+    /// The best line found from every ply of the current line, built up as the search returns
+    pv_table: Vec<Vec<Move>>,
+    /// The principal variation of the last depth, which is searched first on the next one
+    previous_pv: Vec<Move>,
+    /// Kept from one search to the next, so the next move can build on what was found
+    tt: TranspositionTable,
+    /// The killer moves, history scores and counter moves that order the quiet moves
+    heuristics: Heuristics,
+    /// The moves from the root to the current position
+    line: Vec<Move>,
+}
+
+impl Default for Searcher {
+    fn default() -> Self {
+        Searcher::new()
+    }
+}
+
+impl Searcher {
+    pub fn new() -> Searcher {
+        Searcher::with_evaluator(StandardEvaluator::default())
+    }
+}
+
+impl<E: Evaluator> Searcher<E> {
+    pub fn with_evaluator(evaluator: E) -> Searcher<E> {
+        Searcher {
+            evaluator,
+            limits: SearchLimits::default(),
+            start: Instant::now(),
+            nodes: 0,
+            can_stop: false,
+            stopped: false,
+            pv_table: vec![Vec::new(); MAX_PLY + 1],
+            previous_pv: Vec::new(),
+            tt: TranspositionTable::default(),
+            heuristics: Heuristics::new(),
+            line: Vec::with_capacity(MAX_PLY),
+        }
+    }
+
+    pub fn get_evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    pub fn get_tt(&self) -> &TranspositionTable {
+        &self.tt
+    }
+
+    /// Changes the size of the transposition table, which forgets everything in it
+    pub fn set_hash_size(&mut self, megabytes: usize) {
+        self.tt.resize(megabytes);
+    }
+
+    /// Forgets the results of the earlier searches, for example when a new game starts
+    pub fn clear_hash(&mut self) {
+        self.tt.clear();
+        self.heuristics = Heuristics::new();
+    }
+
+    /// Searches the position for the best move of the side to move
+    /// The first depth always finishes, so a move is found even if the limits are very tight
+    pub fn search(&mut self, board: &Board, limits: SearchLimits) -> SearchResult {
+        self.limits = limits;
+        self.start = Instant::now();
+        self.nodes = 0;
+        self.can_stop = false;
+        self.stopped = false;
+        self.previous_pv.clear();
+        self.tt.new_search();
+        self.heuristics.new_search();
+        self.line.clear();
+
+        let mut board = board.clone();
+        // a depth of 0 would not look at a single move, so at least one is searched
+        let max_depth = limits
+            .depth
+            .unwrap_or(MAX_PLY as u32)
+            .clamp(1, MAX_PLY as u32);
+        let mut result = SearchResult {
+            best_move: None,
+            score: 0,
+            pv: Vec::new(),
+            nodes: 0,
+            depth: 0,
+            hashfull: 0,
+        };
+
+        for depth in 1..=max_depth {
+            let score = self.negamax(&mut board, depth, 0, -INFINITY, INFINITY, true);
+            if self.stopped {
+                break;
+            }
+            let pv = self.pv_table[0].clone();
+
+            debug!(
+                "depth {} score {} nodes {} pv {}",
+                depth,
+                score,
+                self.nodes,
+                pv.iter()
+                    .map(|mov| mov.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+
+            self.previous_pv = pv.clone();
+            self.can_stop = true;
+            result = SearchResult {
+                best_move: pv.first().copied(),
+                score,
+                pv,
+                nodes: self.nodes,
+                depth,
+                hashfull: 0,
+            };
+
+            // there are no moves, or a mate was found that a deeper search can not improve
+            if result.best_move.is_none() || MATE - score.abs() <= depth as i32 {
+                break;
+            }
+        }
+
+        result.nodes = self.nodes;
+        result.hashfull = self.tt.hashfull();
+        result
+    }
+
+    /// Returns true once one of the limits is reached
+    fn should_stop(&mut self) -> bool {
+        if !self.can_stop {
+            return false;
+        }
+
+        let out_of_nodes = self.limits.nodes.is_some_and(|nodes| self.nodes >= nodes);
+        let out_of_time = self.nodes.is_multiple_of(CLOCK_INTERVAL)
+            && self
+                .limits
+                .time
+                .is_some_and(|time| self.start.elapsed() >= time);
+
+        self.stopped = out_of_nodes || out_of_time;
+        self.stopped
+    }
+
+    /// Returns the score of the position from the point of view of the side to move,
+    /// looking depth half moves ahead, and fills the table with the best line from here.
+    /// Scores at or below alpha mean the position is no better than one found before,
+    /// scores at or above beta mean the opponent will not allow it
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: u32,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+        follow_pv: bool,
+    ) -> i32 {
+        self.pv_table[ply].clear();
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+
+        let turn = board.get_turn();
+        let moves = board.get_all_legal_moves_for(turn);
+        if moves.is_empty() {
+            return if board.is_in_check(turn) {
+                -MATE + ply as i32
+            } else {
+                0
+            };
+        }
+
+        if ply > 0
+            && (board.repetition_count() > 1
+                || board.get_modifiers().halfmove_clock >= 100
+                || board.is_insufficient_material())
+        {
+            return 0;
+        }
+
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiescence(board, ply, alpha, beta);
+        }
+
+        // a position that was searched deep enough before does not have to be searched again,
+        // except at the root, which has to come up with a move
+        let hash = board.hash();
+        let entry = self.tt.probe(hash, ply);
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth >= depth) {
+            let cutoff = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+            if cutoff {
+                if let Some(mov) = entry.best_move {
+                    self.pv_table[ply].push(mov);
+                }
+                return entry.score;
+            }
+        }
+
+        // the best line of the last depth is searched first, which makes the cutoffs come sooner,
+        // otherwise the best move the table knows of
+        let pv_move = self.previous_pv.get(ply).copied().filter(|_| follow_pv);
+        let first = pv_move.or(entry.and_then(|entry| entry.best_move));
+        let previous = self.line.last().copied();
+        let mut picker = MovePicker::new(moves, first, &self.heuristics, ply, previous);
+
+        let original_alpha = alpha;
+        let mut best = -INFINITY;
+        let mut best_move = None;
+
+        while let Some(mov) = picker.next(board, &self.heuristics) {
+            let quiet = !is_noisy(board, mov);
+            let undo = board.make_move_no_checks(mov);
+            self.line.push(mov);
+            let score = -self.negamax(
+                board,
+                depth - 1,
+                ply + 1,
+                -beta,
+                -alpha,
+                pv_move == Some(mov),
+            );
+            self.line.pop();
+            board.unmake_move(undo);
+
+            if self.stopped {
+                return 0;
+            }
+
+            best = best.max(score);
+            if score > alpha {
+                alpha = score;
+                best_move = Some(mov);
+                let (line, rest) = self.pv_table.split_at_mut(ply + 1);
+                line[ply].clear();
+                line[ply].push(mov);
+                line[ply].extend_from_slice(&rest[0]);
+
+                if alpha >= beta {
+                    // a quiet move that is this good is likely to be good in similar positions
+                    if quiet {
+                        self.heuristics.update(turn, ply, previous, mov, depth);
+                    }
+                    break;
+                }
+            }
+        }
+
+        let bound = if best >= beta {
+            Bound::Lower
+        } else if best > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        self.tt.store(
+            hash,
+            ply,
+            TtEntry {
+                best_move,
+                score: best,
+                depth,
+                bound,
+            },
+        );
+
+        best
+    }
+
+    /// Returns the score of the position once the captures on the board are played out.
+    /// The side to move can stop taking whenever the position is good enough for it,
+    /// except when it is in check, then every way out of the check is searched.
+    /// Captures that lose material by the exchange evaluation are skipped,
+    /// the others are searched the most valuable victim first
+    fn quiescence(&mut self, board: &mut Board, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.pv_table[ply].clear();
+        self.nodes += 1;
+        if self.should_stop() {
+            return 0;
+        }
+
+        let turn = board.get_turn();
+        let in_check = board.is_in_check(turn);
+        let moves = board.get_all_legal_moves_for(turn);
+        if moves.is_empty() {
+            return if in_check { -MATE + ply as i32 } else { 0 };
+        }
+
+        if ply >= MAX_PLY {
+            return self.evaluator.evaluate(board);
+        }
+
+        let mut best = -INFINITY;
+        let mut picker = if in_check {
+            MovePicker::new(moves, None, &self.heuristics, ply, None)
+        } else {
+            // standing pat: not taking anything is an option as well
+            best = self.evaluator.evaluate(board);
+            if best >= beta {
+                return best;
+            }
+            alpha = alpha.max(best);
+            MovePicker::captures(moves)
+        };
+
+        while let Some(mov) = picker.next(board, &self.heuristics) {
+            let undo = board.make_move_no_checks(mov);
+            let score = -self.quiescence(board, ply + 1, -beta, -alpha);
+            board.unmake_move(undo);
+
+            if self.stopped {
+                return 0;
+            }
+
+            best = best.max(score);
+            if score > alpha {
+                alpha = score;
+                let (line, rest) = self.pv_table.split_at_mut(ply + 1);
+                line[ply].clear();
+                line[ply].push(mov);
+                line[ply].extend_from_slice(&rest[0]);
+
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+}
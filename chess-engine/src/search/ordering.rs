@@ -0,0 +1,326 @@
+/// Ordering module
+/// Contains the move picker, which hands out the moves of a position in the order
+/// they are most likely to be good, so the search finds its cutoffs early
+use super::MAX_PLY;
+use chess_base::board::Board;
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use std::cmp::Reverse;
+
+/// History scores stay between minus and plus this value
+const HISTORY_MAX: i32 = 16_384;
+
+/// What the search learned about quiet moves, which helps to order them in other positions
+pub struct Heuristics {
+    /// Two quiet moves per ply that caused a cutoff, the most recent one first
+    killers: Vec<[Option<Move>; 2]>,
+    /// How well every quiet move did, by color and from and to square
+    history: Box<[[[i32; 64]; 64]; 2]>,
+    /// The quiet move that refuted every move, by the from and to square of that move
+    counter_moves: Box<[[Option<Move>; 64]; 64]>,
+}
+
+impl Default for Heuristics {
+    fn default() -> Self {
+        Heuristics::new()
+    }
+}
+
+impl Heuristics {
+    pub fn new() -> Heuristics {
+        Heuristics {
+            killers: vec![[None; 2]; MAX_PLY + 1],
+            history: Box::new([[[0; 64]; 64]; 2]),
+            counter_moves: Box::new([[None; 64]; 64]),
+        }
+    }
+
+    /// Forgets the killers, which belong to the position the last search started from,
+    /// and halves the history so the next search can outweigh it
+    pub fn new_search(&mut self) {
+        self.killers.fill([None; 2]);
+        for score in self.history.iter_mut().flatten().flatten() {
+            *score /= 2;
+        }
+    }
+
+    /// Remembers a quiet move that caused a cutoff.
+    /// previous is the move that led to the position, which the move is a good answer to
+    pub fn update(
+        &mut self,
+        color: Color,
+        ply: usize,
+        previous: Option<Move>,
+        mov: Move,
+        depth: u32,
+    ) {
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(mov) {
+            killers[1] = killers[0];
+            killers[0] = Some(mov);
+        }
+
+        // deep cutoffs count for more, and the score slows down as it gets near the limit
+        let bonus = (depth * depth).min(HISTORY_MAX as u32) as i32;
+        let (from, to) = squares(mov);
+        let score = &mut self.history[color_index(color)][from][to];
+        *score += bonus - *score * bonus / HISTORY_MAX;
+
+        if let Some(previous) = previous {
+            let (from, to) = squares(previous);
+            self.counter_moves[from][to] = Some(mov);
+        }
+    }
+
+    fn history(&self, color: Color, mov: Move) -> i32 {
+        let (from, to) = squares(mov);
+        self.history[color_index(color)][from][to]
+    }
+}
+
+/// The stages of the move picker, in the order it goes through them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    TtMove,
+    ScoreCaptures,
+    GoodCaptures,
+    Killers,
+    CounterMove,
+    ScoreQuiets,
+    Quiets,
+    BadCaptures,
+    Done,
+}
+
+/// Hands out the legal moves of a position one at a time:
+/// first the move from the transposition table or the last principal variation,
+/// then the captures that do not lose material by the exchange evaluation, the most valuable
+/// victim taken by the least valuable attacker first, then the killer moves and the counter move,
+/// then the quiet moves by their history score and last the captures that lose material.
+/// Each stage is only sorted once it is reached, as a cutoff often comes before that.
+/// Moves that score the same keep the order they were generated in, so the order is always the same
+pub struct MovePicker {
+    stage: Stage,
+    /// The moves that were not handed out or sorted into a stage yet
+    moves: Vec<Move>,
+    tt_move: Option<Move>,
+    killers: [Option<Move>; 2],
+    counter_move: Option<Move>,
+    /// Stops after the good captures, for the quiescence search
+    captures_only: bool,
+    /// The moves of the current stage, the next one last
+    queue: Vec<Move>,
+    /// The captures that lose material, the next one last
+    bad_captures: Vec<Move>,
+}
+
+impl MovePicker {
+    /// Creates a picker for all the moves, previous is the move that led to the position
+    pub fn new(
+        moves: Vec<Move>,
+        tt_move: Option<Move>,
+        heuristics: &Heuristics,
+        ply: usize,
+        previous: Option<Move>,
+    ) -> MovePicker {
+        let counter_move = previous.and_then(|previous| {
+            let (from, to) = squares(previous);
+            heuristics.counter_moves[from][to]
+        });
+
+        MovePicker {
+            stage: Stage::TtMove,
+            moves,
+            tt_move,
+            killers: heuristics.killers[ply],
+            counter_move,
+            captures_only: false,
+            queue: Vec::new(),
+            bad_captures: Vec::new(),
+        }
+    }
+
+    /// Creates a picker that only hands out the captures and queen promotions
+    /// that do not lose material
+    pub fn captures(moves: Vec<Move>) -> MovePicker {
+        MovePicker {
+            stage: Stage::ScoreCaptures,
+            moves,
+            tt_move: None,
+            killers: [None; 2],
+            counter_move: None,
+            captures_only: true,
+            queue: Vec::new(),
+            bad_captures: Vec::new(),
+        }
+    }
+
+    /// Returns the next move, None once all moves were handed out
+    pub fn next(&mut self, board: &Board, heuristics: &Heuristics) -> Option<Move> {
+        loop {
+            match self.stage {
+                Stage::TtMove => {
+                    self.stage = Stage::ScoreCaptures;
+                    if let Some(mov) = self.tt_move.filter(|&mov| self.take(mov)) {
+                        return Some(mov);
+                    }
+                }
+                Stage::ScoreCaptures => {
+                    let (noisy, quiet): (Vec<Move>, Vec<Move>) = std::mem::take(&mut self.moves)
+                        .into_iter()
+                        .partition(|&mov| is_noisy(board, mov));
+                    self.moves = quiet;
+
+                    let mut good = Vec::new();
+                    let mut bad = Vec::new();
+                    for mov in noisy {
+                        let see = board.see(mov);
+                        if see >= 0 {
+                            good.push((mov, mvv_lva(board, mov)));
+                        } else {
+                            bad.push((mov, see));
+                        }
+                    }
+                    self.queue = sorted(good);
+                    self.bad_captures = sorted(bad);
+                    self.stage = Stage::GoodCaptures;
+                }
+                Stage::GoodCaptures => match self.queue.pop() {
+                    Some(mov) => return Some(mov),
+                    None if self.captures_only => self.stage = Stage::Done,
+                    None => self.stage = Stage::Killers,
+                },
+                Stage::Killers => {
+                    // a killer is taken out of the killers so it is not handed out twice
+                    let killer = self.killers.iter_mut().find_map(Option::take);
+                    match killer {
+                        Some(mov) if self.take(mov) => return Some(mov),
+                        Some(_) => (),
+                        None => self.stage = Stage::CounterMove,
+                    }
+                }
+                Stage::CounterMove => {
+                    self.stage = Stage::ScoreQuiets;
+                    if let Some(mov) = self.counter_move.filter(|&mov| self.take(mov)) {
+                        return Some(mov);
+                    }
+                }
+                Stage::ScoreQuiets => {
+                    let color = board.get_turn();
+                    let quiets = std::mem::take(&mut self.moves)
+                        .into_iter()
+                        .map(|mov| (mov, heuristics.history(color, mov)))
+                        .collect();
+                    self.queue = sorted(quiets);
+                    self.stage = Stage::Quiets;
+                }
+                Stage::Quiets => match self.queue.pop() {
+                    Some(mov) => return Some(mov),
+                    None => {
+                        self.queue = std::mem::take(&mut self.bad_captures);
+                        self.stage = Stage::BadCaptures;
+                    }
+                },
+                Stage::BadCaptures => match self.queue.pop() {
+                    Some(mov) => return Some(mov),
+                    None => self.stage = Stage::Done,
+                },
+                Stage::Done => return None,
+            }
+        }
+    }
+
+    /// Takes a move out of the moves that are left, returns false if it is not one of them
+    fn take(&mut self, mov: Move) -> bool {
+        match self.moves.iter().position(|&other| other == mov) {
+            Some(index) => {
+                self.moves.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Returns true for the moves the quiescence search plays: captures and queen promotions
+pub fn is_noisy(board: &Board, mov: Move) -> bool {
+    board.is_capture(mov)
+        || matches!(
+            mov,
+            Move::Promotion {
+                promotion: Pieces::Queen,
+                ..
+            }
+        )
+}
+
+/// Sorts the moves by their score, the highest last so they can be popped off in order.
+/// The sort is stable, so moves with the same score come out in the order they came in
+fn sorted(mut moves: Vec<(Move, i32)>) -> Vec<Move> {
+    moves.sort_by_key(|&(_, score)| Reverse(score));
+    moves.into_iter().rev().map(|(mov, _)| mov).collect()
+}
+
+/// Scores a capture by the most valuable victim, and among those the least valuable attacker
+fn mvv_lva(board: &Board, mov: Move) -> i32 {
+    let (from, to) = squares(mov);
+    let piece_at = |index: usize| {
+        board
+            .get_piece(Position::from_index(index))
+            .map(|piece| piece.get_piece_type())
+    };
+
+    let victim = match mov {
+        Move::EnPassant { .. } => Some(Pieces::Pawn),
+        _ => piece_at(to),
+    };
+    let promotion = match mov {
+        Move::Promotion { promotion, .. } => rank(promotion),
+        _ => 0,
+    };
+    let attacker = piece_at(from).map_or(0, rank);
+
+    (victim.map_or(0, rank) + promotion) * 8 - attacker
+}
+
+/// Orders the piece types by their value
+fn rank(piece_type: Pieces) -> i32 {
+    match piece_type {
+        Pieces::Pawn => 1,
+        Pieces::Knight => 2,
+        Pieces::Bishop => 3,
+        Pieces::Rook => 4,
+        Pieces::Queen => 5,
+        Pieces::King => 6,
+    }
+}
+
+/// Returns the indices of the squares the move goes from and to, castling moves the king
+fn squares(mov: Move) -> (usize, usize) {
+    match mov {
+        Move::Normal { from, to }
+        | Move::Promotion { from, to, .. }
+        | Move::EnPassant { from, to } => (from.to_index(), to.to_index()),
+        Move::Castle { color, castle_type } => {
+            let rank = match color {
+                Color::White => 0,
+                Color::Black => 56,
+            };
+            let to = match castle_type {
+                Castles::KingSide => 6,
+                Castles::QueenSide => 2,
+            };
+            (rank + 4, rank + to)
+        }
+    }
+}
+
+fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
@@ -0,0 +1,361 @@
+/// Transposition module
+/// Contains the transposition table, which remembers what the search found out about a position
+/// so it does not have to search it again when the position comes up through other moves
+use super::MATE_BOUND;
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The size of the table the searcher starts with, in megabytes
+pub const DEFAULT_HASH_MB: usize = 16;
+/// The largest table there is room for, in megabytes
+pub const MAX_HASH_MB: usize = 65_536;
+
+/// How many entries share a bucket, the entry to replace is picked from these
+const BUCKET_SIZE: usize = 4;
+/// How many entries hashfull looks at
+const HASHFULL_SAMPLE: usize = 1000;
+/// The generation wraps around at this value
+const GENERATIONS: u8 = 64;
+
+/// What the stored score says about the real score of the position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is exact
+    Exact,
+    /// The real score is at least the stored one, the search stopped at a move that was too good
+    Lower,
+    /// The real score is at most the stored one, no move reached the score the search hoped for
+    Upper,
+}
+
+/// What the table knows about a position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtEntry {
+    /// The best move found, None if no move was better than the others
+    pub best_move: Option<Move>,
+    /// The score from the point of view of the side to move
+    /// Mate scores count the half moves from the position the table was probed at
+    pub score: i32,
+    /// How many half moves deep the position was searched
+    pub depth: u32,
+    pub bound: Bound,
+}
+
+/// One entry as it is kept in the table
+/// The key is stored xor the data, so an entry that was torn by two threads writing it
+/// at the same time does not match its key anymore and is ignored
+#[derive(Default)]
+struct Slot {
+    key: AtomicU64,
+    data: AtomicU64,
+}
+
+#[derive(Default)]
+struct Bucket {
+    slots: [Slot; BUCKET_SIZE],
+}
+
+/// A fixed size hash table of search results, keyed by the Zobrist hash of the position
+/// Probing and storing only need a shared reference and do not lock,
+/// so threads searching at the same time can share one table
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    /// Counts the searches, entries of older searches are replaced first
+    generation: u8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        TranspositionTable::new(DEFAULT_HASH_MB)
+    }
+}
+
+impl TranspositionTable {
+    /// Creates an empty table that takes up about the given number of megabytes,
+    /// but no more than MAX_HASH_MB
+    pub fn new(megabytes: usize) -> TranspositionTable {
+        let bytes = megabytes.min(MAX_HASH_MB).saturating_mul(1024 * 1024);
+        let count = (bytes / std::mem::size_of::<Bucket>()).max(1);
+        TranspositionTable {
+            buckets: (0..count).map(|_| Bucket::default()).collect(),
+            generation: 0,
+        }
+    }
+
+    /// Returns the size of the table in megabytes
+    pub fn get_size_mb(&self) -> usize {
+        self.buckets.len() * std::mem::size_of::<Bucket>() / (1024 * 1024)
+    }
+
+    /// Returns the number of entries the table has room for
+    pub fn capacity(&self) -> usize {
+        self.buckets.len() * BUCKET_SIZE
+    }
+
+    /// Changes the size of the table, which empties it
+    pub fn resize(&mut self, megabytes: usize) {
+        *self = TranspositionTable::new(megabytes);
+    }
+
+    /// Empties the table
+    pub fn clear(&mut self) {
+        for slot in self.buckets.iter_mut().flat_map(|bucket| &mut bucket.slots) {
+            *slot.key.get_mut() = 0;
+            *slot.data.get_mut() = 0;
+        }
+        self.generation = 0;
+    }
+
+    /// Tells the table a new search starts, so the entries of the last ones can make room
+    pub fn new_search(&mut self) {
+        self.generation = (self.generation + 1) % GENERATIONS;
+    }
+
+    /// Returns how full the table is in permille, counting the entries of the current search
+    /// Only the first thousand entries are looked at, which is plenty to estimate it
+    pub fn hashfull(&self) -> u32 {
+        let entries = self
+            .buckets
+            .iter()
+            .flat_map(|bucket| &bucket.slots)
+            .take(HASHFULL_SAMPLE);
+
+        let mut sampled = 0;
+        let mut used = 0;
+        for slot in entries {
+            sampled += 1;
+            let data = slot.data.load(Ordering::Relaxed);
+            if data != 0 && Data(data).generation() == self.generation {
+                used += 1;
+            }
+        }
+
+        used * 1000 / sampled
+    }
+
+    /// Returns what the table knows about the position with the given hash,
+    /// ply is the number of half moves from the root of the search to the position
+    pub fn probe(&self, hash: u64, ply: usize) -> Option<TtEntry> {
+        self.bucket(hash).slots.iter().find_map(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            let key = slot.key.load(Ordering::Relaxed);
+            (data != 0 && key ^ data == hash).then(|| Data(data).to_entry(ply))
+        })
+    }
+
+    /// Stores the result of a search of the position with the given hash,
+    /// ply is the number of half moves from the root of the search to the position.
+    /// An entry of the same position is replaced unless it is a lot deeper,
+    /// otherwise the entry that is the least useful is, preferring old and shallow ones
+    pub fn store(&self, hash: u64, ply: usize, entry: TtEntry) {
+        let bucket = self.bucket(hash);
+        let generation = self.generation;
+
+        let existing = bucket.slots.iter().find(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            data != 0 && slot.key.load(Ordering::Relaxed) ^ data == hash
+        });
+
+        let (slot, mut entry) = match existing {
+            Some(slot) => {
+                let old = Data(slot.data.load(Ordering::Relaxed));
+                if entry.bound != Bound::Exact
+                    && old.generation() == generation
+                    && old.depth() > entry.depth + 2
+                {
+                    return;
+                }
+                // a search that did not find a best move keeps the one from before
+                let mut entry = entry;
+                if entry.best_move.is_none() {
+                    entry.best_move = old.best_move();
+                }
+                (slot, entry)
+            }
+            None => {
+                let worth = |slot: &Slot| {
+                    let data = slot.data.load(Ordering::Relaxed);
+                    if data == 0 {
+                        return i32::MIN;
+                    }
+                    let data = Data(data);
+                    let age = (GENERATIONS + generation - data.generation()) % GENERATIONS;
+                    data.depth() as i32 - 8 * age as i32
+                };
+                // the first of the least useful entries, so the choice does not depend on luck
+                let slot = bucket
+                    .slots
+                    .iter()
+                    .min_by_key(|&slot| worth(slot))
+                    .unwrap_or(&bucket.slots[0]);
+                (slot, entry)
+            }
+        };
+
+        entry.score = score_to_table(entry.score, ply);
+        let data = Data::new(&entry, generation).0;
+        slot.key.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    fn bucket(&self, hash: u64) -> &Bucket {
+        // maps the hash onto the buckets without needing a power of two of them
+        let index = ((hash as u128 * self.buckets.len() as u128) >> 64) as usize;
+        &self.buckets[index]
+    }
+}
+
+/// Mate scores are stored as the distance from the position instead of from the root,
+/// so they stay right when the position is found again at another ply
+fn score_to_table(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score + ply as i32
+    } else if score <= -MATE_BOUND {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+fn score_from_table(score: i32, ply: usize) -> i32 {
+    if score >= MATE_BOUND {
+        score - ply as i32
+    } else if score <= -MATE_BOUND {
+        score + ply as i32
+    } else {
+        score
+    }
+}
+
+/// An entry packed into 64 bits:
+/// the move in bits 0-15, the score in bits 16-31, the depth in bits 32-39,
+/// the bound in bits 40-41 and the generation in bits 42-47.
+/// The bound is never 0, so data of 0 is an empty slot
+#[derive(Clone, Copy)]
+struct Data(u64);
+
+impl Data {
+    fn new(entry: &TtEntry, generation: u8) -> Data {
+        let bound: u64 = match entry.bound {
+            Bound::Exact => 1,
+            Bound::Lower => 2,
+            Bound::Upper => 3,
+        };
+
+        Data(
+            entry.best_move.map_or(0, encode_move) as u64
+                | (entry.score as i16 as u16 as u64) << 16
+                | (entry.depth.min(u8::MAX as u32) as u64) << 32
+                | bound << 40
+                | (generation as u64) << 42,
+        )
+    }
+
+    fn best_move(&self) -> Option<Move> {
+        decode_move(self.0 as u16)
+    }
+
+    fn score(&self) -> i32 {
+        (self.0 >> 16) as u16 as i16 as i32
+    }
+
+    fn depth(&self) -> u32 {
+        (self.0 >> 32) as u8 as u32
+    }
+
+    fn bound(&self) -> Bound {
+        match (self.0 >> 40) & 3 {
+            1 => Bound::Exact,
+            2 => Bound::Lower,
+            _ => Bound::Upper,
+        }
+    }
+
+    fn generation(&self) -> u8 {
+        ((self.0 >> 42) & 63) as u8
+    }
+
+    fn to_entry(self, ply: usize) -> TtEntry {
+        TtEntry {
+            best_move: self.best_move(),
+            score: score_from_table(self.score(), ply),
+            depth: self.depth(),
+            bound: self.bound(),
+        }
+    }
+}
+
+/// Packs a move into 16 bits: the from square in bits 0-5, the to square in bits 6-11
+/// and the kind of move in bits 12-15. Castling keeps the color and the side in the squares.
+/// No move packs into 0, as every move has a different from and to square or a kind
+fn encode_move(mov: Move) -> u16 {
+    let pack = |from: Position, to: Position, kind: u16| {
+        from.to_index() as u16 | (to.to_index() as u16) << 6 | kind << 12
+    };
+
+    match mov {
+        Move::Normal { from, to } => pack(from, to, 0),
+        Move::EnPassant { from, to } => pack(from, to, 1),
+        Move::Castle { color, castle_type } => {
+            let color = match color {
+                Color::White => 0,
+                Color::Black => 1,
+            };
+            let castle_type = match castle_type {
+                Castles::KingSide => 0,
+                Castles::QueenSide => 1,
+            };
+            color | castle_type << 6 | 2 << 12
+        }
+        Move::Promotion {
+            from,
+            to,
+            promotion,
+        } => {
+            let kind = match promotion {
+                Pieces::Knight => 3,
+                Pieces::Bishop => 4,
+                Pieces::Rook => 5,
+                _ => 6,
+            };
+            pack(from, to, kind)
+        }
+    }
+}
+
+fn decode_move(bits: u16) -> Option<Move> {
+    if bits == 0 {
+        return None;
+    }
+
+    let from = Position::from_index((bits & 63) as usize);
+    let to = Position::from_index((bits >> 6 & 63) as usize);
+    let promotion = |promotion| Move::Promotion {
+        from,
+        to,
+        promotion,
+    };
+
+    Some(match bits >> 12 {
+        0 => Move::Normal { from, to },
+        1 => Move::EnPassant { from, to },
+        2 => Move::Castle {
+            color: match bits & 63 {
+                0 => Color::White,
+                _ => Color::Black,
+            },
+            castle_type: match bits >> 6 & 63 {
+                0 => Castles::KingSide,
+                _ => Castles::QueenSide,
+            },
+        },
+        3 => promotion(Pieces::Knight),
+        4 => promotion(Pieces::Bishop),
+        5 => promotion(Pieces::Rook),
+        _ => promotion(Pieces::Queen),
+    })
+}
@@ -0,0 +1,130 @@
+use chess_base::board::Board;
+use chess_engine::evaluate::{EvalParams, Evaluator, Score, StandardEvaluator};
+
+fn evaluate(fen: &str) -> i32 {
+    StandardEvaluator::default().evaluate(&Board::from_fen(fen).unwrap())
+}
+
+/// Returns the same position with the colors swapped, flipping the board top to bottom
+fn mirror(fen: &str) -> String {
+    let fields: Vec<&str> = fen.split(' ').collect();
+    let swap_case = |text: &str| -> String {
+        text.chars()
+            .map(|c| {
+                if c.is_ascii_uppercase() {
+                    c.to_ascii_lowercase()
+                } else {
+                    c.to_ascii_uppercase()
+                }
+            })
+            .collect()
+    };
+    let placement = fields[0].split('/').rev().collect::<Vec<_>>().join("/");
+    let turn = if fields[1] == "w" { "b" } else { "w" };
+
+    format!(
+        "{} {} {} - {} {}",
+        swap_case(&placement),
+        turn,
+        swap_case(fields[2]),
+        fields[4],
+        fields[5]
+    )
+}
+
+#[test]
+fn the_starting_position_is_even() {
+    assert_eq!(evaluate(&Board::new_arranged().to_fen()), 0);
+    assert_eq!(
+        evaluate("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"),
+        0
+    );
+}
+
+#[test]
+fn scores_are_from_the_side_to_move() {
+    let fens = [
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+
+    for fen in fens {
+        let black = fen.replace(" w ", " b ");
+        assert_eq!(evaluate(fen), -evaluate(&black), "{}", fen);
+        // the evaluation does not care which color is which
+        assert_eq!(evaluate(fen), evaluate(&mirror(fen)), "{}", fen);
+    }
+}
+
+#[test]
+fn counts_material() {
+    // an extra queen
+    assert!(evaluate("4k3/8/8/8/8/8/8/3QK3 w - - 0 1") > 800);
+    assert!(evaluate("4k3/8/8/8/8/8/8/3QK3 b - - 0 1") < -800);
+    // a knight is worth about three pawns
+    let knight = evaluate("4k3/8/8/8/8/8/8/1N2K3 w - - 0 1");
+    assert!((250..400).contains(&knight), "{}", knight);
+}
+
+#[test]
+fn knows_about_pawns_and_kings() {
+    // a passed pawn is worth more the further it got
+    assert!(
+        evaluate("4k3/8/4P3/8/8/8/8/4K3 w - - 0 1") > evaluate("4k3/8/8/8/8/4P3/8/4K3 w - - 0 1")
+    );
+    // doubled and isolated pawns are weaker than connected ones
+    assert!(
+        evaluate("4k3/8/8/8/8/3P4/3P4/4K3 w - - 0 1")
+            < evaluate("4k3/8/8/8/8/4P3/3P4/4K3 w - - 0 1")
+    );
+    // in the middlegame the king wants to hide behind its pawns
+    assert!(
+        evaluate("rnbq1rk1/pppppppp/8/8/8/8/PPPPPPPP/RNBQ1RK1 w - - 0 1")
+            > evaluate("rnbq1rk1/pppppppp/8/8/8/5K2/PPPPPPPP/RNBQ1R2 w - - 0 1")
+    );
+    // in the endgame it wants to come to the center
+    assert!(evaluate("8/8/8/8/4K3/8/8/k7 w - - 0 1") > evaluate("8/8/8/8/8/8/8/k6K w - - 0 1"));
+}
+
+#[test]
+fn the_terms_can_be_tuned() {
+    let board = Board::from_fen("4k3/pppp4/8/8/8/8/PPPP4/2B1KB2 w - - 0 1").unwrap();
+    let default = StandardEvaluator::default();
+
+    let tuned = StandardEvaluator::new(EvalParams {
+        bishop_pair: Score::new(100, 100),
+        ..EvalParams::default()
+    });
+    assert_eq!(
+        tuned.evaluate(&board) - default.evaluate(&board),
+        100 - default.params.bishop_pair.taper(default.phase(&board), 24)
+    );
+
+    // with everything but the piece values turned off the evaluation only counts material
+    let params = EvalParams {
+        piece_values: [
+            Score::new(1, 1),
+            Score::new(3, 3),
+            Score::new(3, 3),
+            Score::new(5, 5),
+            Score::new(9, 9),
+            Score::new(0, 0),
+        ],
+        psts: [[Score::default(); 64]; 6],
+        bishop_pair: Score::default(),
+        mobility: [Score::default(); 6],
+        king_shield: Score::default(),
+        king_zone_attack: Score::default(),
+        doubled_pawn: Score::default(),
+        isolated_pawn: Score::default(),
+        passed_pawn: [Score::default(); 8],
+        ..EvalParams::default()
+    };
+    let material = StandardEvaluator::new(params);
+    assert_eq!(material.evaluate(&board), 6);
+    assert_eq!(
+        material.evaluate(&Board::from_fen("4k3/8/8/8/8/8/8/3QK3 b - - 0 1").unwrap()),
+        -9
+    );
+}
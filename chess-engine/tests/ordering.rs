@@ -0,0 +1,128 @@
+use chess_base::board::Board;
+use chess_base::core::mov::Move;
+use chess_engine::search::{Heuristics, MovePicker};
+
+// white can take the queen and the rook with the pawn, a free pawn with the queen,
+// and a pawn that the bishop defends with the queen
+const CAPTURES: &str = "4kb2/1p4p1/2r1q3/3P4/8/8/1Q6/7K w - - 0 1";
+
+fn san(board: &Board, sans: &str) -> Move {
+    Move::from_san(sans, board).unwrap()
+}
+
+/// Returns all the moves the picker hands out
+fn picked(board: &Board, mut picker: MovePicker, heuristics: &Heuristics) -> Vec<String> {
+    let mut sans = Vec::new();
+    while let Some(mov) = picker.next(board, heuristics) {
+        sans.push(mov.to_san(board));
+    }
+    sans
+}
+
+#[test]
+fn hands_out_every_move_once() {
+    let board = Board::from_fen(CAPTURES).unwrap();
+    let heuristics = Heuristics::new();
+    let moves = board.get_all_legal_moves_for(board.get_turn());
+    let tt_move = san(&board, "Kg1");
+
+    let sans = picked(
+        &board,
+        MovePicker::new(moves.clone(), Some(tt_move), &heuristics, 0, None),
+        &heuristics,
+    );
+
+    assert_eq!(sans.len(), moves.len());
+    let mut unique = sans.clone();
+    unique.sort();
+    unique.dedup();
+    assert_eq!(unique.len(), moves.len());
+
+    // the move from the table, the good captures, the quiet moves, then the losing capture
+    assert_eq!(sans[..4], ["Kg1", "dxe6", "dxc6", "Qxb7"]);
+    assert_eq!(sans.last().unwrap(), "Qxg7");
+
+    // the order does not change from one run to the next
+    let again = picked(
+        &board,
+        MovePicker::new(moves, Some(tt_move), &heuristics, 0, None),
+        &heuristics,
+    );
+    assert_eq!(sans, again);
+
+    // a move from the table that is not legal here is skipped
+    let sans = picked(
+        &board,
+        MovePicker::new(
+            board.get_all_legal_moves_for(board.get_turn()),
+            Some(san(&Board::new_arranged(), "e4")),
+            &heuristics,
+            0,
+            None,
+        ),
+        &heuristics,
+    );
+    assert_eq!(
+        sans.len(),
+        board.get_all_legal_moves_for(board.get_turn()).len()
+    );
+    assert_eq!(sans[0], "dxe6");
+}
+
+#[test]
+fn the_quiescence_search_only_gets_good_captures() {
+    let board = Board::from_fen(CAPTURES).unwrap();
+    let heuristics = Heuristics::new();
+
+    let sans = picked(
+        &board,
+        MovePicker::captures(board.get_all_legal_moves_for(board.get_turn())),
+        &heuristics,
+    );
+    assert_eq!(sans, ["dxe6", "dxc6", "Qxb7"]);
+}
+
+#[test]
+fn quiet_moves_that_caused_cutoffs_come_first() {
+    let board = Board::from_fen(CAPTURES).unwrap();
+    let mut heuristics = Heuristics::new();
+    let color = board.get_turn();
+    let killer = san(&board, "Qa3");
+    let counter = san(&board, "Kh2");
+    let history = san(&board, "Qc1");
+    let previous = san(&Board::new_arranged(), "e4");
+
+    heuristics.update(color, 2, None, killer, 3);
+    heuristics.update(color, 5, Some(previous), counter, 3);
+    // a cutoff at a deeper depth counts for more in the history
+    heuristics.update(color, 6, None, history, 2);
+    heuristics.update(color, 6, None, history, 6);
+
+    let sans = picked(
+        &board,
+        MovePicker::new(
+            board.get_all_legal_moves_for(board.get_turn()),
+            None,
+            &heuristics,
+            2,
+            Some(previous),
+        ),
+        &heuristics,
+    );
+    assert_eq!(sans[..6], ["dxe6", "dxc6", "Qxb7", "Qa3", "Kh2", "Qc1"]);
+
+    // the killers belong to the last search, the history is kept
+    heuristics.new_search();
+    let sans = picked(
+        &board,
+        MovePicker::new(
+            board.get_all_legal_moves_for(board.get_turn()),
+            None,
+            &heuristics,
+            2,
+            None,
+        ),
+        &heuristics,
+    );
+    assert_eq!(sans[3], "Qc1");
+}
@@ -0,0 +1,153 @@
+use chess_base::board::Board;
+use chess_base::core::mov::Move;
+use chess_engine::search::{search, SearchLimits, Searcher, MATE};
+use std::time::{Duration, Instant};
+
+fn best_move_san(fen: &str, limits: SearchLimits) -> String {
+    let board = Board::from_fen(fen).unwrap();
+    let result = search(&board, limits);
+    result.best_move.unwrap().to_san(&board)
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(3));
+
+    assert_eq!(result.best_move.unwrap().to_san(&board), "Ra8#");
+    assert_eq!(result.score, MATE - 1);
+    assert_eq!(result.mate_in(), Some(1));
+}
+
+#[test]
+fn finds_mate_in_two() {
+    let board = Board::from_fen("kbK5/pp6/1P6/8/8/8/8/R7 w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(4));
+
+    assert_eq!(result.best_move.unwrap().to_san(&board), "Ra6");
+    assert_eq!(result.mate_in(), Some(2));
+    assert_eq!(result.pv.len(), 3);
+}
+
+#[test]
+fn sees_being_mated() {
+    let board = Board::from_fen("6k1/8/8/8/8/8/5PPP/r6K w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(2));
+
+    assert_eq!(result.score, -MATE);
+    assert_eq!(result.best_move, None);
+
+    let board = Board::from_fen("7k/8/8/8/8/8/r7/1r5K w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(3));
+    assert_eq!(result.best_move, None);
+}
+
+#[test]
+fn wins_material() {
+    assert_eq!(
+        best_move_san("4k3/8/8/3q4/4P3/8/8/4K3 w - - 0 1", SearchLimits::depth(2)),
+        "exd5"
+    );
+    // the knight fork wins the queen a move later
+    assert_eq!(
+        best_move_san("4k3/8/8/1q6/4N3/8/8/4K3 w - - 0 1", SearchLimits::depth(3)),
+        "Nd6+"
+    );
+}
+
+#[test]
+fn looks_past_the_horizon_at_the_captures() {
+    // at depth 1 the pawn looks free, the quiescence search sees the recapture
+    let board = Board::from_fen("4k3/8/3p4/4p3/8/8/8/4QK2 w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(1));
+    assert_ne!(result.best_move.unwrap().to_san(&board), "Qxe5+");
+    assert!(result.score > 500, "{}", result.score);
+
+    // a piece nothing defends is still taken
+    let board = Board::from_fen("4k3/8/8/3r4/8/8/3R4/3RK3 w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(1));
+    assert_eq!(result.best_move.unwrap().to_san(&board), "Rxd5");
+    assert!(result.score > 900, "{}", result.score);
+}
+
+#[test]
+fn avoids_stalemate_when_winning() {
+    let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 w - - 0 1").unwrap();
+    let result = search(&board, SearchLimits::depth(3));
+
+    assert!(result.mate_in().is_some());
+    let mut after = board.clone();
+    after.make_move(result.best_move.unwrap()).unwrap();
+    assert!(!after.in_stalemate(after.get_turn()));
+}
+
+#[test]
+fn the_pv_is_a_line_of_legal_moves() {
+    let board =
+        Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+            .unwrap();
+    let result = search(&board, SearchLimits::depth(3));
+
+    assert_eq!(result.depth, 3);
+    assert_eq!(result.pv.first().copied(), result.best_move);
+    let mut board = board;
+    for mov in &result.pv {
+        board.make_move(*mov).unwrap();
+    }
+}
+
+#[test]
+fn stops_at_the_limits() {
+    let board = Board::new_arranged();
+
+    let result = search(&board, SearchLimits::depth(2));
+    assert_eq!(result.depth, 2);
+
+    // a depth of 0 still finds a move
+    let result = search(&board, SearchLimits::depth(0));
+    assert_eq!(result.depth, 1);
+    assert!(result.best_move.is_some());
+
+    let result = search(&board, SearchLimits::nodes(2000));
+    assert!(result.best_move.is_some());
+    // the first depth always finishes, after that the search stops right at the limit
+    assert!(result.nodes <= 2000, "{} nodes", result.nodes);
+
+    let start = Instant::now();
+    let result = Searcher::new().search(&board, SearchLimits::time(Duration::from_millis(200)));
+    assert!(result.best_move.is_some());
+    assert!(start.elapsed() < Duration::from_millis(1000));
+}
+
+#[test]
+fn searches_are_repeatable() {
+    let board = Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+        .unwrap();
+    let first = Searcher::new().search(&board, SearchLimits::depth(3));
+    let second = Searcher::new().search(&board, SearchLimits::depth(3));
+
+    assert_eq!(first, second);
+    assert!(first
+        .best_move
+        .is_some_and(|mov| matches!(mov, Move::Normal { .. })));
+}
+
+#[test]
+fn the_table_is_kept_between_searches() {
+    let board = Board::from_fen("r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3")
+        .unwrap();
+    let mut searcher = Searcher::new();
+    searcher.set_hash_size(1);
+    let first = searcher.search(&board, SearchLimits::depth(4));
+    let second = searcher.search(&board, SearchLimits::depth(4));
+
+    // the second search finds the answer in the table
+    assert_eq!(first.best_move, second.best_move);
+    assert_eq!(first.score, second.score);
+    assert!(second.nodes < first.nodes / 10, "{} nodes", second.nodes);
+    assert!(first.hashfull > 0);
+
+    searcher.clear_hash();
+    assert_eq!(searcher.get_tt().hashfull(), 0);
+    assert_eq!(searcher.search(&board, SearchLimits::depth(4)), first);
+}
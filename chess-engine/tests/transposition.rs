@@ -0,0 +1,157 @@
+use chess_base::board::Board;
+use chess_base::core::castles::Castles;
+use chess_base::core::color::Color;
+use chess_base::core::mov::Move;
+use chess_base::core::pieces::Pieces;
+use chess_base::core::position::Position;
+use chess_engine::search::{Bound, TranspositionTable, TtEntry, MATE};
+
+fn entry(best_move: Option<Move>, score: i32, depth: u32, bound: Bound) -> TtEntry {
+    TtEntry {
+        best_move,
+        score,
+        depth,
+        bound,
+    }
+}
+
+#[test]
+fn stores_and_finds_entries() {
+    let table = TranspositionTable::new(1);
+    let board = Board::new_arranged();
+    let moves = [
+        Move::from_san("e4", &board).unwrap(),
+        Move::EnPassant {
+            from: Position::from_an('e', 5),
+            to: Position::from_an('d', 6),
+        },
+        Move::Castle {
+            color: Color::Black,
+            castle_type: Castles::QueenSide,
+        },
+        Move::Promotion {
+            from: Position::from_an('b', 7),
+            to: Position::from_an('a', 8),
+            promotion: Pieces::Knight,
+        },
+    ];
+
+    for (i, &mov) in moves.iter().enumerate() {
+        let hash = board
+            .hash()
+            .wrapping_add((i as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let stored = entry(Some(mov), -37, 5, Bound::Lower);
+        table.store(hash, 3, stored);
+        assert_eq!(table.probe(hash, 3), Some(stored));
+    }
+
+    assert_eq!(table.probe(board.hash() ^ 1, 0), None);
+    table.store(7, 0, entry(None, 0, 1, Bound::Upper));
+    assert_eq!(table.probe(7, 0).unwrap().best_move, None);
+}
+
+#[test]
+fn mate_scores_count_from_where_they_are_found() {
+    let table = TranspositionTable::new(1);
+    // mate in 3 half moves from a position 4 half moves into the search
+    table.store(42, 4, entry(None, MATE - 7, 8, Bound::Exact));
+
+    assert_eq!(table.probe(42, 4).unwrap().score, MATE - 7);
+    // found again 2 half moves into a later search, the mate is still 3 half moves away
+    assert_eq!(table.probe(42, 2).unwrap().score, MATE - 5);
+
+    table.store(43, 4, entry(None, -MATE + 6, 8, Bound::Exact));
+    assert_eq!(table.probe(43, 1).unwrap().score, -MATE + 3);
+    // normal scores stay as they are
+    table.store(44, 4, entry(None, 250, 8, Bound::Exact));
+    assert_eq!(table.probe(44, 9).unwrap().score, 250);
+}
+
+#[test]
+fn replaces_the_least_useful_entries() {
+    let mut table = TranspositionTable::new(0);
+    // a table of a single bucket
+    assert_eq!(table.capacity(), 4);
+
+    let mov = Move::from_san("d4", &Board::new_arranged()).unwrap();
+    table.store(1, 0, entry(Some(mov), 10, 8, Bound::Exact));
+    // a much shallower result does not replace a deep one of the same search
+    table.store(1, 0, entry(None, 20, 2, Bound::Lower));
+    assert_eq!(table.probe(1, 0).unwrap().score, 10);
+    // a result almost as deep does, and keeps the move it did not find itself
+    table.store(1, 0, entry(None, 30, 7, Bound::Upper));
+    assert_eq!(
+        table.probe(1, 0),
+        Some(entry(Some(mov), 30, 7, Bound::Upper))
+    );
+
+    for hash in 2..=4 {
+        table.store(hash, 0, entry(None, 0, hash as u32, Bound::Exact));
+    }
+    // the bucket is full, the shallowest entry makes room
+    table.store(5, 0, entry(None, 0, 1, Bound::Exact));
+    assert!(table.probe(2, 0).is_none());
+    assert!(table.probe(1, 0).is_some());
+
+    // after a few searches old entries give way to new ones, however deep they are
+    for _ in 0..3 {
+        table.new_search();
+    }
+    for hash in 6..=9 {
+        table.store(hash, 0, entry(None, 0, 1, Bound::Exact));
+    }
+    assert!((6..=9).all(|hash| table.probe(hash, 0).is_some()));
+    assert!(table.probe(1, 0).is_none());
+}
+
+#[test]
+fn reports_how_full_it_is() {
+    let mut table = TranspositionTable::new(1);
+    assert_eq!(table.get_size_mb(), 1);
+    assert_eq!(table.hashfull(), 0);
+
+    for hash in 0..50_000u64 {
+        table.store(
+            hash.wrapping_mul(0x9e37_79b9_7f4a_7c15),
+            0,
+            entry(None, 0, 1, Bound::Exact),
+        );
+    }
+    let full = table.hashfull();
+    assert!((500..=1000).contains(&full), "{}", full);
+
+    // entries of earlier searches do not count
+    table.new_search();
+    assert_eq!(table.hashfull(), 0);
+
+    table.resize(2);
+    assert_eq!(table.get_size_mb(), 2);
+    assert_eq!(table.capacity(), 2 * TranspositionTable::new(1).capacity());
+}
+
+#[test]
+fn threads_can_share_a_table() {
+    let table = TranspositionTable::new(1);
+
+    std::thread::scope(|scope| {
+        for thread in 0..4u64 {
+            let table = &table;
+            scope.spawn(move || {
+                for i in 0..1000u64 {
+                    let hash = (thread * 1000 + i + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+                    table.store(hash, 0, entry(None, i as i32, 3, Bound::Exact));
+                }
+            });
+        }
+    });
+
+    let found = (0..4000u64)
+        .filter(|i| {
+            let hash = (i + 1).wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            table
+                .probe(hash, 0)
+                .is_some_and(|entry| entry.score == (i % 1000) as i32)
+        })
+        .count();
+    assert!(found > 3900, "{}", found);
+}
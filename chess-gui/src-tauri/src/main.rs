@@ -5,96 +5,106 @@
 
 use chess_base as base;
 
-use base::board::*;
 // use base::core::castles::Castles;
 // use base::core::color::Color;
 use base::core::mov::Move;
-use base::core::position::Position;
+use base::core::uci::ParseMoveError;
+use base::game::Game;
 use std::sync::Mutex;
+use std::time::Instant;
 use tauri::command;
 
 #[command]
-fn get_fen() -> String {
-    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR".to_string()
+fn get_fen(game: tauri::State<Mutex<Game>>) -> String {
+    game.lock().unwrap().get_board().to_fen()
+}
+
+/// Returns how the game ended, or None while it is still going on
+#[command]
+fn get_status(game: tauri::State<Mutex<Game>>) -> Option<String> {
+    let status = game.lock().unwrap().status();
+    status.is_over().then(|| status.to_string())
+}
+
+/// Returns the moves that led to the position on the board, in standard algebraic notation
+#[command]
+fn get_moves(game: tauri::State<Mutex<Game>>) -> Vec<String> {
+    let game = game.lock().unwrap();
+    game.current_line()[..game.ply()]
+        .iter()
+        .map(|&id| game.node(id).get_san().to_string())
+        .collect()
 }
 
 #[derive(serde::Deserialize)]
 struct MoveData {
     from: String,
     to: String,
+    /// The letter of the piece a pawn promotes to, like q for a queen
+    promotion: Option<char>,
 }
 
+/// Makes the move and returns it in standard algebraic notation for the move list
 #[command]
-fn make_move(move_data: MoveData, board: tauri::State<Mutex<Board>>) -> bool {
-    let mut board = board.lock().unwrap();
-    let from_position = Position::from_an(
-        move_data.from.chars().nth(0).unwrap(),
-        move_data.from.chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-    );
-    let to_position = Position::from_an(
-        move_data.to.chars().nth(0).unwrap(),
-        move_data.to.chars().nth(1).unwrap().to_digit(10).unwrap() as u8,
-    );
-
-    println!("from: {:?}, to: {:?}", from_position, to_position);
-
-    let mov = Move::Normal {
-        from: from_position,
-        to: to_position,
-    };
-
-    //no way, am too lazy to implement this
-
-    // if from_position.get_x() == 4
-    //     && from_position.get_y() == 0
-    //     && to_position.get_x() == 6
-    //     && to_position.get_y() == 0
-    // {
-    //     mov = Move::Castle {
-    //         color: Color::White,
-    //         castle_type: Castles::KingSide,
-    //     };
-    // } else if from_position.get_x() == 4
-    //     && from_position.get_y() == 0
-    //     && to_position.get_x() == 2
-    //     && to_position.get_y() == 0
-    // {
-    //     mov = Move::Castle {
-    //         color: Color::White,
-    //         castle_type: Castles::QueenSide,
-    //     };
-    // } else if from_position.get_x() == 4
-    //     && from_position.get_y() == 7
-    //     && to_position.get_x() == 6
-    //     && to_position.get_y() == 7
-    // {
-    //     mov = Move::Castle {
-    //         color: Color::Black,
-    //         castle_type: Castles::KingSide,
-    //     };
-    // } else if from_position.get_x() == 4
-    //     && from_position.get_y() == 7
-    //     && to_position.get_x() == 2
-    //     && to_position.get_y() == 7
-    // {
-    //     mov = Move::Castle {
-    //         color: Color::Black,
-    //         castle_type: Castles::QueenSide,
-    //     };
-    // }
-
-    board.make_move(mov)
+fn make_move(
+    move_data: MoveData,
+    game: tauri::State<Mutex<Game>>,
+    thinking: tauri::State<Mutex<Instant>>,
+) -> Result<String, String> {
+    let mut game = game.lock().unwrap();
+
+    let mut uci = format!("{}{}", move_data.from, move_data.to);
+    uci.extend(move_data.promotion.map(|c| c.to_ascii_lowercase()));
+    let mov: Move = uci
+        .parse()
+        .map_err(|error: ParseMoveError| error.to_string())?;
+    // the board turns a king move of two squares into castling
+    let mov = game.get_board().resolve_move(mov);
+
+    game.make_move(mov).map_err(|error| error.to_string())?;
+
+    let mut thinking = thinking.lock().unwrap();
+    let cursor = game.cursor();
+    let node = game.node_mut(cursor);
+    node.time_spent = Some(thinking.elapsed());
+    *thinking = Instant::now();
+
+    Ok(node.get_san().to_string())
+}
+
+/// Takes back the last move, returns false if there is none
+#[command]
+fn undo(game: tauri::State<Mutex<Game>>, thinking: tauri::State<Mutex<Instant>>) -> bool {
+    *thinking.lock().unwrap() = Instant::now();
+    game.lock().unwrap().undo()
+}
+
+/// Plays the last move that was taken back again, returns false if there is none
+#[command]
+fn redo(game: tauri::State<Mutex<Game>>, thinking: tauri::State<Mutex<Instant>>) -> bool {
+    *thinking.lock().unwrap() = Instant::now();
+    game.lock().unwrap().redo()
+}
+
+/// Writes the game with its result so far to a PGN file
+#[command]
+fn save_pgn(path: String, game: tauri::State<Mutex<Game>>) -> Result<(), String> {
+    let mut pgn = game.lock().unwrap().to_pgn();
+    pgn.set_tag("Event", "Casual game");
+    pgn.set_tag("Site", "chess-gui");
+
+    std::fs::write(path, pgn.to_pgn()).map_err(|error| error.to_string())
 }
 
 fn main() {
     // ... setup logger and start_game code
 
-    let board = Board::new_arranged();
-    let board = Mutex::new(board);
-
     tauri::Builder::default()
-        .manage(board)
-        .invoke_handler(tauri::generate_handler![get_fen, make_move])
+        .manage(Mutex::new(Game::new()))
+        .manage(Mutex::new(Instant::now()))
+        .invoke_handler(tauri::generate_handler![
+            get_fen, get_status, get_moves, make_move, undo, redo, save_pgn
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
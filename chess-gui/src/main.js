@@ -19,29 +19,102 @@ async function drop(ev) {
     ? targetElement.parentElement.dataset.position
     : targetElement.dataset.position;
 
-  // Check if the move is legal
+  // a pawn that reaches the last rank has to be told what to become
+  let promotion = null;
+  const rank = toPosition[1];
+  if (draggedPiece.dataset.piece.toLowerCase() === "p" && (rank === "8" || rank === "1")) {
+    promotion = prompt("Promote to (q, r, b or n)", "q");
+    if (!promotion) {
+      return;
+    }
+  }
 
-  const isLegalMove = await makeMove(fromPosition, toPosition);
-  if (isLegalMove) {
-    // Check if the drop target is a piece or a cell
-    let targetCell = targetElement.classList.contains("piece") ? targetElement.parentElement : targetElement;
+  // the board is drawn again from the game, which also shows what castling,
+  // en passant and promotions did to the other squares
+  if (await makeMove(fromPosition, toPosition, promotion)) {
+    await refresh();
 
-    // Remove the existing piece in the target cell, if any
-    while (targetCell.firstChild) {
-      targetCell.removeChild(targetCell.firstChild);
+    const status = await window.__TAURI__.invoke("get_status");
+    if (status) {
+      alert("Game over: " + status);
     }
+  }
+}
+
 
-    // Append the dragged piece to the target cell
-    targetCell.appendChild(draggedPiece);
+async function makeMove(from, to, promotion) {
+  const moveData = { from, to, promotion };
+  try {
+    await window.__TAURI__.invoke("make_move", { moveData });
+    return true;
+  } catch (error) {
+    // the error explains why the move was rejected
+    console.warn(error);
+    return false;
   }
 }
 
 
-async function makeMove(from, to) {
-  const moveData = { from, to };
-  return await window.__TAURI__.invoke("make_move", { moveData });
+// takes back the last move and shows the position before it
+async function takeBack() {
+  if (await window.__TAURI__.invoke("undo")) {
+    await refresh();
+  }
+}
+
+// plays the move that was taken back again
+async function playAgain() {
+  if (await window.__TAURI__.invoke("redo")) {
+    await refresh();
+  }
 }
 
+// renders the board and the move list again from the game
+async function refresh() {
+  for (const piece of document.querySelectorAll(".piece")) {
+    piece.remove();
+  }
+  renderGame(await window.__TAURI__.invoke("get_fen"));
+
+  document.getElementById("moves").replaceChildren();
+  for (const san of await window.__TAURI__.invoke("get_moves")) {
+    addToMoveList(san);
+  }
+}
+
+
+// asks where to save the game and writes it there as PGN
+async function saveGame() {
+  const path = prompt("Save the game to", "game.pgn");
+  if (!path) {
+    return;
+  }
+
+  try {
+    await window.__TAURI__.invoke("save_pgn", { path });
+  } catch (error) {
+    alert("Could not save the game: " + error);
+  }
+}
+
+
+// white's moves start a new numbered entry, black's moves are added to the last one
+function addToMoveList(san) {
+  const moveList = document.getElementById("moves");
+  const last = moveList.lastElementChild;
+
+  if (last && last.childElementCount === 1) {
+    const move = document.createElement("span");
+    move.textContent = san;
+    last.appendChild(move);
+  } else {
+    const entry = document.createElement("li");
+    const move = document.createElement("span");
+    move.textContent = san;
+    entry.appendChild(move);
+    moveList.appendChild(entry);
+  }
+}
 
 function createChessBoard() {
   for (let i = 0; i < 8; i++) {
@@ -59,11 +132,12 @@ function createChessBoard() {
 
 function renderGame(fen) {
   const cells = document.querySelectorAll(".cell");
-  const x = fen.split("/");
+  // the piece placement field lists the ranks from the eighth down to the first
+  const ranks = fen.split(" ")[0].split("/");
 
-  for (let rank = 7; rank >= 0; rank--) {
+  for (let rank = 0; rank < 8; rank++) {
     let file = 0;
-    for (const char of x[rank]) {
+    for (const char of ranks[rank]) {
       if (Number.isInteger(parseInt(char))) {
         file += parseInt(char);
       } else {
@@ -77,6 +151,7 @@ function renderGame(fen) {
           
           img.classList.add("piece");
           img.id = "piece-" + position;
+          img.dataset.piece = char;
           img.setAttribute("draggable", "true");
           img.setAttribute("ondragstart", "drag(event)");
           cell.appendChild(img);